@@ -1,18 +1,28 @@
 use axum::{
     extract::{Path, Query, State},
-    http::StatusCode,
-    response::{IntoResponse, Json},
+    http::{HeaderMap, StatusCode},
+    response::{
+        IntoResponse, Json,
+        sse::{Event, KeepAlive, Sse},
+    },
 };
 use chrono::NaiveDate;
+use futures_util::stream;
 use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::convert::Infallible;
+use std::hash::{Hash, Hasher};
+use tokio::sync::broadcast;
+use tracing::warn;
 use uuid::Uuid;
 
 use crate::actions::json_error;
-use crate::coverage::CoverageHexFeature;
+use crate::coverage::{CoverageBin, CoverageHexFeature, CoverageNotification};
 use crate::coverage_cache::CoverageCache;
 use crate::coverage_repo::CoverageRepository;
 use crate::fixes::Fix;
 use crate::fixes_repo::FixesRepository;
+use crate::terrain::TerrainClass;
 use crate::web::AppState;
 
 // ============================================================================
@@ -53,6 +63,22 @@ pub struct CoverageQueryParams {
 
     /// Maximum number of hexes to return
     pub limit: Option<i64>,
+
+    /// Time-bin granularity to read from (daily, weekly, or monthly). If
+    /// omitted, the coarsest bin that still fully covers the requested
+    /// date range is picked automatically.
+    pub bin: Option<CoverageBin>,
+
+    /// Only return hexes whose terrain enrichment (see `crate::terrain`)
+    /// classifies them as this terrain type, e.g. `mountainous` to find
+    /// hexes over mountainous terrain. Hexes not yet enriched never match.
+    pub terrain_class: Option<TerrainClass>,
+
+    /// Only return hexes whose terrain clearance (`min_altitude_msl_feet -
+    /// ground_elevation_msl_feet`) is at or below this many feet, to find
+    /// coverage that's shallow over the ground rather than a true radio
+    /// gap. Hexes not yet enriched never match.
+    pub max_terrain_clearance_feet: Option<i32>,
 }
 
 // ============================================================================
@@ -130,6 +156,9 @@ pub async fn get_coverage_hexes(
             params.min_altitude,
             params.max_altitude,
             limit,
+            params.bin,
+            params.terrain_class,
+            params.max_terrain_clearance_feet,
         )
         .await
     {
@@ -263,3 +292,205 @@ pub async fn get_hex_fixes(
         }
     }
 }
+
+/// Query parameters for the live coverage stream endpoint
+#[derive(Debug, Deserialize)]
+pub struct CoverageStreamQueryParams {
+    /// H3 resolution to filter updates to (3, 4, 5, 6, 7, or 8)
+    pub resolution: Option<i16>,
+
+    /// Bounding box: western longitude. Must be given together with south/east/north.
+    pub west: Option<f64>,
+
+    /// Bounding box: eastern longitude
+    pub east: Option<f64>,
+
+    /// Bounding box: southern latitude
+    pub south: Option<f64>,
+
+    /// Bounding box: northern latitude
+    pub north: Option<f64>,
+}
+
+/// Returns true if the notified hex's approximate center falls within the
+/// given bounding box. `west` may be greater than `east` when the box
+/// crosses the International Date Line.
+fn notification_in_bbox(
+    notification: &CoverageNotification,
+    west: f64,
+    south: f64,
+    east: f64,
+    north: f64,
+) -> bool {
+    let Ok((lat, lng)) = notification.centroid() else {
+        return false;
+    };
+
+    let in_lat_range = lat >= south && lat <= north;
+    let in_lng_range = if west <= east {
+        lng >= west && lng <= east
+    } else {
+        lng >= west || lng <= east
+    };
+
+    in_lat_range && in_lng_range
+}
+
+/// GET /data/coverage/stream
+/// Server-sent events stream of live `CoverageHexFeature` GeoJSON updates,
+/// fed by the `coverage_updates` Postgres NOTIFY channel (see
+/// `crate::coverage_stream`). Filters by resolution and, if given, a
+/// bounding box, so each subscriber only sees the hexes it's watching.
+pub async fn get_coverage_stream(
+    Query(params): Query<CoverageStreamQueryParams>,
+    State(state): State<AppState>,
+) -> impl IntoResponse {
+    metrics::counter!("coverage.api.stream.subscriptions_total").increment(1);
+
+    let resolution = params.resolution.unwrap_or(7);
+    if ![3, 4, 5, 6, 7, 8].contains(&resolution) {
+        return json_error(
+            StatusCode::BAD_REQUEST,
+            "Resolution must be 3, 4, 5, 6, 7, or 8",
+        )
+        .into_response();
+    }
+
+    let bbox = match (params.west, params.south, params.east, params.north) {
+        (Some(west), Some(south), Some(east), Some(north)) => Some((west, south, east, north)),
+        (None, None, None, None) => None,
+        _ => {
+            return json_error(
+                StatusCode::BAD_REQUEST,
+                "bbox filtering requires west, south, east, and north together",
+            )
+            .into_response();
+        }
+    };
+
+    let rx = state.coverage_stream.subscribe();
+
+    let event_stream = stream::unfold(rx, move |mut rx| async move {
+        loop {
+            match rx.recv().await {
+                Ok(notification) => {
+                    if notification.resolution != resolution {
+                        continue;
+                    }
+
+                    if let Some((west, south, east, north)) = bbox
+                        && !notification_in_bbox(&notification, west, south, east, north)
+                    {
+                        continue;
+                    }
+
+                    let feature = match CoverageHexFeature::from_notification(&notification) {
+                        Ok(feature) => feature,
+                        Err(e) => {
+                            warn!("Failed to build coverage feature from notification: {}", e);
+                            continue;
+                        }
+                    };
+
+                    let Ok(event) = Event::default().json_data(&feature) else {
+                        continue;
+                    };
+
+                    return Some((Ok::<Event, Infallible>(event), rx));
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    metrics::counter!("coverage.api.stream.lagged_total").increment(skipped);
+                    continue;
+                }
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    });
+
+    Sse::new(event_stream)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+/// Query parameters for the coverage MVT tile endpoint
+#[derive(Debug, Deserialize)]
+pub struct CoverageTileQueryParams {
+    /// Start date for coverage (YYYY-MM-DD)
+    pub start_date: Option<NaiveDate>,
+
+    /// End date for coverage (YYYY-MM-DD)
+    pub end_date: Option<NaiveDate>,
+
+    /// Filter by receiver ID
+    pub receiver_id: Option<Uuid>,
+
+    /// Minimum altitude MSL (feet)
+    pub min_altitude: Option<i32>,
+
+    /// Maximum altitude MSL (feet)
+    pub max_altitude: Option<i32>,
+}
+
+/// GET /data/coverage/tiles/{z}/{x}/{y}
+/// Binary Mapbox Vector Tile of coverage hexes intersecting the given XYZ
+/// tile, so web maps can use a drop-in XYZ tile URL for the coverage
+/// heatmap instead of fetching and clipping raw GeoJSON client-side.
+pub async fn get_coverage_tile(
+    Path((z, x, y)): Path<(u8, u32, u32)>,
+    Query(params): Query<CoverageTileQueryParams>,
+    State(state): State<AppState>,
+) -> impl IntoResponse {
+    metrics::counter!("coverage.api.tiles.requests_total").increment(1);
+
+    let end_date = params
+        .end_date
+        .unwrap_or_else(|| chrono::Utc::now().date_naive());
+    let start_date = params
+        .start_date
+        .unwrap_or_else(|| end_date - chrono::Duration::days(30));
+
+    let repo = CoverageRepository::new(state.pool.clone());
+
+    match repo
+        .get_coverage_mvt(
+            z,
+            x,
+            y,
+            start_date,
+            end_date,
+            params.receiver_id,
+            params.min_altitude,
+            params.max_altitude,
+        )
+        .await
+    {
+        Ok(tile) => {
+            metrics::counter!("coverage.api.tiles.success_total").increment(1);
+
+            // No existing dependency computes content hashes, so a std-only
+            // DefaultHasher is good enough to give map clients a strong
+            // (unprefixed) ETag for caching.
+            let mut hasher = DefaultHasher::new();
+            tile.hash(&mut hasher);
+            let etag = format!("\"{:x}\"", hasher.finish());
+
+            let mut headers = HeaderMap::new();
+            headers.insert(
+                "content-type",
+                "application/vnd.mapbox-vector-tile".parse().unwrap(),
+            );
+            headers.insert("cache-control", "public, max-age=60".parse().unwrap());
+            headers.insert("etag", etag.parse().unwrap());
+
+            (StatusCode::OK, headers, tile).into_response()
+        }
+        Err(e) => {
+            metrics::counter!("coverage.api.tiles.errors_total").increment(1);
+            json_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                &format!("Failed to get coverage tile: {}", e),
+            )
+            .into_response()
+        }
+    }
+}
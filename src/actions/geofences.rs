@@ -12,11 +12,11 @@ use uuid::Uuid;
 use crate::actions::{DataListResponse, DataResponse, json_error};
 use crate::auth::AuthUser;
 use crate::geofence::{
-    CreateGeofenceRequest, GeofenceDetailResponse, GeofenceExitEventsResponse,
-    GeofenceListResponse, GeofenceWithCounts, LinkAircraftRequest, SubscribeToGeofenceRequest,
+    CreateGeofenceRequest, DwellIntervalsResponse, ExitEventFilter, GeofenceDetailResponse,
+    GeofenceEntryEventsResponse, GeofenceExitEventsResponse, GeofenceListResponse,
+    GeofenceWithCounts, LinkAircraftRequest, NotificationChannel, SubscribeToGeofenceRequest,
     UpdateGeofenceRequest,
 };
-use crate::geofence_repo::GeofenceRepository;
 use crate::web::AppState;
 
 /// Query parameters for listing geofences
@@ -33,6 +33,18 @@ pub struct ExitEventsQuery {
     pub limit: Option<i64>,
 }
 
+/// Query parameters for unsubscribing from a geofence
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnsubscribeQuery {
+    #[serde(default = "default_unsubscribe_channel")]
+    pub channel: NotificationChannel,
+}
+
+fn default_unsubscribe_channel() -> NotificationChannel {
+    NotificationChannel::Email
+}
+
 // ==================== Geofence CRUD ====================
 
 /// GET /data/geofences - List user's geofences
@@ -41,7 +53,7 @@ pub async fn list_geofences(
     State(state): State<AppState>,
     Query(query): Query<ListGeofencesQuery>,
 ) -> impl IntoResponse {
-    let repo = GeofenceRepository::new(state.pool);
+    let repo = state.geofence_repo;
     let user = &auth_user.0;
 
     // Use club_id from query or fall back to user's club
@@ -96,7 +108,7 @@ pub async fn create_geofence(
         .into_response();
     }
 
-    let repo = GeofenceRepository::new(state.pool);
+    let repo = state.geofence_repo;
 
     match repo.create(user.id, req).await {
         Ok(geofence) => (
@@ -125,7 +137,7 @@ pub async fn get_geofence(
     State(state): State<AppState>,
     Path(geofence_id): Path<Uuid>,
 ) -> impl IntoResponse {
-    let repo = GeofenceRepository::new(state.pool);
+    let repo = state.geofence_repo;
     let user = &auth_user.0;
 
     match repo.get_by_id(geofence_id).await {
@@ -177,7 +189,7 @@ pub async fn update_geofence(
         return json_error(StatusCode::BAD_REQUEST, &msg).into_response();
     }
 
-    let repo = GeofenceRepository::new(state.pool);
+    let repo = state.geofence_repo;
     let user = &auth_user.0;
 
     // First get the geofence to check permission
@@ -241,7 +253,7 @@ pub async fn delete_geofence(
     State(state): State<AppState>,
     Path(geofence_id): Path<Uuid>,
 ) -> impl IntoResponse {
-    let repo = GeofenceRepository::new(state.pool);
+    let repo = state.geofence_repo;
     let user = &auth_user.0;
 
     // First get the geofence to check permission
@@ -289,7 +301,7 @@ pub async fn get_geofence_aircraft(
     State(state): State<AppState>,
     Path(geofence_id): Path<Uuid>,
 ) -> impl IntoResponse {
-    let repo = GeofenceRepository::new(state.pool);
+    let repo = state.geofence_repo;
     let user = &auth_user.0;
 
     // Check permission to view geofence
@@ -326,7 +338,7 @@ pub async fn add_geofence_aircraft(
     Path(geofence_id): Path<Uuid>,
     Json(req): Json<LinkAircraftRequest>,
 ) -> impl IntoResponse {
-    let repo = GeofenceRepository::new(state.pool);
+    let repo = state.geofence_repo;
     let user = &auth_user.0;
 
     // Check permission to modify geofence
@@ -365,7 +377,7 @@ pub async fn remove_geofence_aircraft(
     State(state): State<AppState>,
     Path((geofence_id, aircraft_id)): Path<(Uuid, Uuid)>,
 ) -> impl IntoResponse {
-    let repo = GeofenceRepository::new(state.pool);
+    let repo = state.geofence_repo;
     let user = &auth_user.0;
 
     // Check permission to modify geofence
@@ -413,7 +425,7 @@ pub async fn get_geofence_subscribers(
     State(state): State<AppState>,
     Path(geofence_id): Path<Uuid>,
 ) -> impl IntoResponse {
-    let repo = GeofenceRepository::new(state.pool);
+    let repo = state.geofence_repo;
     let user = &auth_user.0;
 
     // Check permission to view geofence
@@ -457,7 +469,7 @@ pub async fn subscribe_to_geofence(
     Path(geofence_id): Path<Uuid>,
     Json(req): Json<SubscribeToGeofenceRequest>,
 ) -> impl IntoResponse {
-    let repo = GeofenceRepository::new(state.pool);
+    let repo = state.geofence_repo;
     let user = &auth_user.0;
 
     // Check that geofence exists and user can access it
@@ -479,11 +491,11 @@ pub async fn subscribe_to_geofence(
     }
 
     match repo
-        .add_subscriber(geofence_id, user.id, req.send_email)
+        .add_subscriber(geofence_id, user.id, req.channels)
         .await
     {
-        Ok(subscriber) => {
-            (StatusCode::CREATED, Json(DataResponse { data: subscriber })).into_response()
+        Ok(subscribers) => {
+            (StatusCode::CREATED, Json(DataListResponse { data: subscribers })).into_response()
         }
         Err(e) => {
             error!(geofence_id = %geofence_id, error = %e, "Failed to subscribe to geofence");
@@ -492,13 +504,15 @@ pub async fn subscribe_to_geofence(
     }
 }
 
-/// DELETE /data/geofences/{geofence_id}/subscribers/{user_id} - Unsubscribe from geofence
+/// DELETE /data/geofences/{geofence_id}/subscribers/{user_id} - Unsubscribe from geofence.
+/// Defaults to the email channel; pass `?channel=webhook` etc. to remove another.
 pub async fn unsubscribe_from_geofence(
     auth_user: AuthUser,
     State(state): State<AppState>,
     Path((geofence_id, user_id)): Path<(Uuid, Uuid)>,
+    Query(query): Query<UnsubscribeQuery>,
 ) -> impl IntoResponse {
-    let repo = GeofenceRepository::new(state.pool);
+    let repo = state.geofence_repo;
     let user = &auth_user.0;
 
     // Users can unsubscribe themselves; owners/admins can unsubscribe anyone
@@ -520,7 +534,10 @@ pub async fn unsubscribe_from_geofence(
         }
     }
 
-    match repo.remove_subscriber(geofence_id, user_id).await {
+    match repo
+        .remove_subscriber(geofence_id, user_id, query.channel)
+        .await
+    {
         Ok(true) => StatusCode::NO_CONTENT.into_response(),
         Ok(false) => json_error(StatusCode::NOT_FOUND, "Subscription not found").into_response(),
         Err(e) => {
@@ -539,7 +556,7 @@ pub async fn get_geofence_events(
     Path(geofence_id): Path<Uuid>,
     Query(query): Query<ExitEventsQuery>,
 ) -> impl IntoResponse {
-    let repo = GeofenceRepository::new(state.pool);
+    let repo = state.geofence_repo;
     let user = &auth_user.0;
 
     // Check permission to view geofence
@@ -578,7 +595,7 @@ pub async fn get_flight_geofence_events(
     State(state): State<AppState>,
     Path(flight_id): Path<Uuid>,
 ) -> impl IntoResponse {
-    let repo = GeofenceRepository::new(state.pool);
+    let repo = state.geofence_repo;
 
     // Note: We don't check geofence permission here because flight events are
     // associated with the flight, and anyone who can view the flight can see
@@ -592,3 +609,70 @@ pub async fn get_flight_geofence_events(
         }
     }
 }
+
+// ==================== Entry Events ====================
+
+/// GET /data/flights/{id}/geofence-entries - Get geofence entry events for a flight
+pub async fn get_flight_geofence_entries(
+    _auth_user: AuthUser,
+    State(state): State<AppState>,
+    Path(flight_id): Path<Uuid>,
+) -> impl IntoResponse {
+    let repo = state.geofence_repo;
+
+    // Note: same reasoning as get_flight_geofence_events - anyone who can view
+    // the flight can see its associated geofence entries.
+
+    match repo.get_entry_events_for_flight(flight_id).await {
+        Ok(events) => Json(GeofenceEntryEventsResponse { events }).into_response(),
+        Err(e) => {
+            error!(flight_id = %flight_id, error = %e, "Failed to get flight geofence entries");
+            json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to get entries").into_response()
+        }
+    }
+}
+
+/// GET /data/flights/{id}/geofence-dwell - Get geofence dwell intervals for a flight
+pub async fn get_flight_geofence_dwell(
+    _auth_user: AuthUser,
+    State(state): State<AppState>,
+    Path(flight_id): Path<Uuid>,
+) -> impl IntoResponse {
+    let repo = state.geofence_repo;
+
+    match repo.get_dwell_intervals_for_flight(flight_id).await {
+        Ok(intervals) => Json(DwellIntervalsResponse { intervals }).into_response(),
+        Err(e) => {
+            error!(flight_id = %flight_id, error = %e, "Failed to get flight dwell intervals");
+            json_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to get dwell intervals",
+            )
+            .into_response()
+        }
+    }
+}
+
+/// Query exit events across all geofences within a time range, with
+/// optional geofence/aircraft/flight filters and keyset pagination, for a
+/// breach-history dashboard.
+/// POST /data/geofences/exit-events/query (with body containing the filter)
+pub async fn query_exit_events(
+    _auth_user: AuthUser,
+    State(state): State<AppState>,
+    Json(filter): Json<ExitEventFilter>,
+) -> impl IntoResponse {
+    let repo = state.geofence_repo;
+
+    match repo.query_exit_events(filter).await {
+        Ok(result) => Json(result).into_response(),
+        Err(e) => {
+            error!(error = %e, "Failed to query exit events");
+            json_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to query exit events",
+            )
+            .into_response()
+        }
+    }
+}
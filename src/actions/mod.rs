@@ -9,8 +9,10 @@ pub mod coverage;
 pub mod devices;
 pub mod fixes;
 pub mod flights;
+pub mod geofences;
 pub mod pilots;
 pub mod receivers;
+pub mod stripe_connect;
 pub mod user_fixes;
 pub mod user_settings;
 pub mod users;
@@ -24,9 +26,11 @@ pub use analytics::*;
 pub use aprs_messages::*;
 pub use auth::*;
 pub use clubs::*;
+pub use coverage::{get_coverage_stream, get_coverage_tile};
 pub use devices::*;
 pub use fixes::*;
 pub use flights::*;
+pub use geofences::*;
 pub use receivers::*;
 pub use user_fixes::*;
 pub use user_settings::*;
@@ -20,7 +20,7 @@ use crate::stripe_client::StripeConfig;
 use crate::stripe_connected_accounts::NewStripeConnectedAccount;
 use crate::stripe_connected_accounts_repo::StripeConnectedAccountsRepository;
 use crate::stripe_webhooks::NewStripeWebhookEvent;
-use crate::stripe_webhooks_repo::StripeWebhookEventsRepository;
+use crate::stripe_webhooks_repo::{DEFAULT_MAX_RETRY_ATTEMPTS, StripeWebhookEventsRepository};
 use crate::web::AppState;
 
 use super::{DataResponse, json_error};
@@ -393,8 +393,17 @@ pub async fn handle_webhook(
         }
         Err(e) => {
             error!(event_type = %event_type, error = %e, "Failed to process webhook event");
-            if let Err(e2) = webhook_repo.mark_failed(&event_id, &e.to_string()).await {
-                error!(error = %e2, "Failed to mark webhook as failed");
+            if let Err(e2) = webhook_repo
+                .mark_for_retry(
+                    &event_id,
+                    &e.to_string(),
+                    chrono::Duration::seconds(30),
+                    chrono::Duration::minutes(30),
+                    DEFAULT_MAX_RETRY_ATTEMPTS,
+                )
+                .await
+            {
+                error!(error = %e2, "Failed to schedule webhook retry");
             }
         }
     }
@@ -491,6 +500,77 @@ async fn process_webhook_event(
     Ok(())
 }
 
+/// Claim and re-process the next webhook event due for retry. On success
+/// the event is marked processed; on failure it's rescheduled via
+/// `mark_for_retry`'s exponential backoff, up to `DEFAULT_MAX_RETRY_ATTEMPTS`.
+/// Returns `Ok(false)` if no events were due, so a caller can loop until
+/// drained.
+pub async fn retry_next_stripe_webhook(
+    state: &AppState,
+    stripe_config: &StripeConfig,
+) -> anyhow::Result<bool> {
+    let webhook_repo = StripeWebhookEventsRepository::new(state.pool.clone());
+    let Some(due) = webhook_repo.claim_due_retries(1).await?.into_iter().next() else {
+        return Ok(false);
+    };
+
+    let event: Event = match serde_json::from_value(due.payload.clone()) {
+        Ok(event) => event,
+        Err(e) => {
+            // A payload that doesn't deserialize can never succeed - abandon it.
+            warn!(
+                stripe_event_id = %due.stripe_event_id,
+                "Malformed stored webhook payload, dead-lettering: {}",
+                e
+            );
+            webhook_repo
+                .dead_letter(&due.stripe_event_id, &e.to_string())
+                .await?;
+            return Ok(true);
+        }
+    };
+
+    match process_webhook_event(state, stripe_config, &due.event_type, &event).await {
+        Ok(()) => {
+            webhook_repo.mark_processed(&due.stripe_event_id).await?;
+        }
+        Err(e) => {
+            warn!(
+                stripe_event_id = %due.stripe_event_id,
+                "Retry of webhook event failed: {}",
+                e
+            );
+            webhook_repo
+                .mark_for_retry(
+                    &due.stripe_event_id,
+                    &e.to_string(),
+                    chrono::Duration::seconds(30),
+                    chrono::Duration::minutes(30),
+                    DEFAULT_MAX_RETRY_ATTEMPTS,
+                )
+                .await?;
+        }
+    }
+
+    Ok(true)
+}
+
+/// Background task draining the Stripe webhook retry queue. Calls
+/// `retry_next_stripe_webhook` in a loop, backing off when the queue is
+/// empty, comparable to `terrain_refresh_task`/`analytics_metrics_task`.
+pub async fn stripe_webhook_retry_task(state: AppState, stripe_config: StripeConfig) {
+    loop {
+        match retry_next_stripe_webhook(&state, &stripe_config).await {
+            Ok(true) => continue,
+            Ok(false) => tokio::time::sleep(std::time::Duration::from_secs(30)).await,
+            Err(e) => {
+                warn!("Stripe webhook retry poll failed: {:#}", e);
+                tokio::time::sleep(std::time::Duration::from_secs(30)).await;
+            }
+        }
+    }
+}
+
 async fn create_account_link(
     stripe_config: &StripeConfig,
     account_id: &stripe::AccountId,
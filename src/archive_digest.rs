@@ -0,0 +1,322 @@
+use anyhow::{Context, Result};
+use chrono::{NaiveDate, Utc};
+use std::collections::HashMap;
+use std::path::Path;
+use tracing::{info, warn};
+
+use crate::archive_email_reporter::ArchiveReport;
+
+/// Rolling trend for a single table across every run in the digest window.
+#[derive(Debug, Clone)]
+pub struct TableTrend {
+    pub table_name: String,
+    pub runs: usize,
+    pub total_rows_archived: usize,
+    pub total_bytes_written: u64,
+    pub avg_duration_secs: f64,
+    pub worst_duration_secs: f64,
+}
+
+/// A day whose archived-table row count deviated sharply from its trailing median,
+/// surfaced so operators can investigate before it's buried in a stream of per-run emails.
+#[derive(Debug, Clone)]
+pub struct AnomalyFlag {
+    pub table_name: String,
+    pub date: NaiveDate,
+    pub count: i64,
+    pub trailing_median: f64,
+}
+
+/// Aggregate digest summarizing a rolling window of prior [`ArchiveReport`]s, giving
+/// operators a longitudinal view instead of a stream of point-in-time emails.
+pub struct ArchiveDigest {
+    pub window_start: NaiveDate,
+    pub window_end: NaiveDate,
+    pub runs_included: usize,
+    pub table_trends: Vec<TableTrend>,
+    pub anomalies: Vec<AnomalyFlag>,
+}
+
+/// How many trailing daily-count entries to compare a day against when flagging anomalies.
+const ANOMALY_TRAILING_WINDOW: usize = 14;
+/// A day is flagged if its count is at least this many times the trailing median (or its
+/// reciprocal), so long as the median itself is non-trivial.
+const ANOMALY_RATIO_THRESHOLD: f64 = 3.0;
+
+impl ArchiveDigest {
+    /// Build a digest from the reports found in the window, oldest first.
+    pub fn from_reports(reports: &[(NaiveDate, ArchiveReport)]) -> Self {
+        let window_start = reports
+            .iter()
+            .map(|(date, _)| *date)
+            .min()
+            .unwrap_or_else(|| Utc::now().date_naive());
+        let window_end = reports
+            .iter()
+            .map(|(date, _)| *date)
+            .max()
+            .unwrap_or_else(|| Utc::now().date_naive());
+
+        let mut trends: HashMap<String, TableTrend> = HashMap::new();
+        for (_, report) in reports {
+            for table in &report.tables {
+                let trend = trends
+                    .entry(table.table_name.clone())
+                    .or_insert_with(|| TableTrend {
+                        table_name: table.table_name.clone(),
+                        runs: 0,
+                        total_rows_archived: 0,
+                        total_bytes_written: 0,
+                        avg_duration_secs: 0.0,
+                        worst_duration_secs: 0.0,
+                    });
+                trend.runs += 1;
+                trend.total_rows_archived += table.rows_deleted;
+                trend.total_bytes_written += table.file_size_bytes;
+                trend.avg_duration_secs += table.duration_secs;
+                trend.worst_duration_secs = trend.worst_duration_secs.max(table.duration_secs);
+            }
+        }
+        let mut table_trends: Vec<TableTrend> = trends.into_values().collect();
+        for trend in &mut table_trends {
+            if trend.runs > 0 {
+                trend.avg_duration_secs /= trend.runs as f64;
+            }
+        }
+        table_trends.sort_by(|a, b| a.table_name.cmp(&b.table_name));
+
+        // Anomaly detection runs against the most recent report's daily_counts, since that's
+        // the freshest and most complete per-day series for each table.
+        let mut anomalies = Vec::new();
+        if let Some((_, latest)) = reports.last() {
+            for (table_name, counts) in &latest.daily_counts {
+                let mut sorted = counts.clone();
+                sorted.sort_by_key(|dc| dc.date);
+
+                for i in 0..sorted.len() {
+                    let start = i.saturating_sub(ANOMALY_TRAILING_WINDOW);
+                    let trailing: Vec<i64> = sorted[start..i].iter().map(|dc| dc.count).collect();
+                    if trailing.len() < 3 {
+                        continue;
+                    }
+
+                    let median = trailing_median(&trailing);
+                    if median <= 0.0 {
+                        continue;
+                    }
+
+                    let count = sorted[i].count as f64;
+                    if count / median >= ANOMALY_RATIO_THRESHOLD
+                        || median / count.max(1.0) >= ANOMALY_RATIO_THRESHOLD
+                    {
+                        anomalies.push(AnomalyFlag {
+                            table_name: table_name.clone(),
+                            date: sorted[i].date,
+                            count: sorted[i].count,
+                            trailing_median: median,
+                        });
+                    }
+                }
+            }
+        }
+        anomalies.sort_by_key(|a| a.date);
+
+        Self {
+            window_start,
+            window_end,
+            runs_included: reports.len(),
+            table_trends,
+            anomalies,
+        }
+    }
+
+    pub fn to_html(&self) -> String {
+        let mut html = format!(
+            r#"<!DOCTYPE html>
+<html>
+<body>
+<h1>SOAR Archive Digest: {} to {}</h1>
+<p>{} archive runs included.</p>
+<h2>Per-table trends</h2>
+<table border="1" cellpadding="6" cellspacing="0">
+<tr><th>Table</th><th>Runs</th><th>Total Rows Archived</th><th>Total Bytes Written</th><th>Avg Duration</th><th>Worst Duration</th></tr>"#,
+            self.window_start, self.window_end, self.runs_included
+        );
+
+        for trend in &self.table_trends {
+            html.push_str(&format!(
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{:.1}s</td><td>{:.1}s</td></tr>",
+                trend.table_name,
+                trend.runs,
+                trend.total_rows_archived,
+                trend.total_bytes_written,
+                trend.avg_duration_secs,
+                trend.worst_duration_secs,
+            ));
+        }
+        html.push_str("</table>");
+
+        if !self.anomalies.is_empty() {
+            html.push_str("<h2>Anomalies</h2><table border=\"1\" cellpadding=\"6\" cellspacing=\"0\"><tr><th>Table</th><th>Date</th><th>Count</th><th>Trailing Median</th></tr>");
+            for anomaly in &self.anomalies {
+                html.push_str(&format!(
+                    "<tr><td>{}</td><td>{}</td><td>{}</td><td>{:.1}</td></tr>",
+                    anomaly.table_name, anomaly.date, anomaly.count, anomaly.trailing_median
+                ));
+            }
+            html.push_str("</table>");
+        }
+
+        html.push_str("</body></html>");
+        html
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = format!(
+            "SOAR Archive Digest: {} to {}\n{} archive runs included.\n\nPer-table trends\n",
+            self.window_start, self.window_end, self.runs_included
+        );
+
+        for trend in &self.table_trends {
+            text.push_str(&format!(
+                "{:<20}  runs={:<4}  rows={:<10}  bytes={:<12}  avg={:.1}s  worst={:.1}s\n",
+                trend.table_name,
+                trend.runs,
+                trend.total_rows_archived,
+                trend.total_bytes_written,
+                trend.avg_duration_secs,
+                trend.worst_duration_secs,
+            ));
+        }
+
+        if !self.anomalies.is_empty() {
+            text.push_str("\nAnomalies\n");
+            for anomaly in &self.anomalies {
+                text.push_str(&format!(
+                    "{}  {}  count={}  trailing_median={:.1}\n",
+                    anomaly.date, anomaly.table_name, anomaly.count, anomaly.trailing_median
+                ));
+            }
+        }
+
+        text
+    }
+}
+
+fn trailing_median(values: &[i64]) -> f64 {
+    let mut sorted = values.to_vec();
+    sorted.sort_unstable();
+    let mid = sorted.len() / 2;
+    if sorted.len().is_multiple_of(2) {
+        (sorted[mid - 1] + sorted[mid]) as f64 / 2.0
+    } else {
+        sorted[mid] as f64
+    }
+}
+
+/// Load every `*-archive_report.json` export (written when `ARCHIVE_EXPORT_METRICS=true`) in
+/// `archive_dir` whose date falls within the trailing `window_days`, oldest first.
+pub fn load_recent_reports(
+    archive_dir: &Path,
+    window_days: i64,
+) -> Result<Vec<(NaiveDate, ArchiveReport)>> {
+    let cutoff = Utc::now().date_naive() - chrono::Duration::days(window_days);
+    let mut reports = Vec::new();
+
+    if !archive_dir.exists() {
+        return Ok(reports);
+    }
+
+    for entry in std::fs::read_dir(archive_dir).context("Failed to read archive directory")? {
+        let path = entry?.path();
+        let Some(file_name) = path.file_name().and_then(|f| f.to_str()) else {
+            continue;
+        };
+        let Some(date_str) = file_name.strip_suffix("-archive_report.json") else {
+            continue;
+        };
+        let Ok(date) = NaiveDate::parse_from_str(date_str, "%Y%m%d") else {
+            continue;
+        };
+        if date < cutoff {
+            continue;
+        }
+
+        match std::fs::read_to_string(&path)
+            .context("Failed to read archive report export")
+            .and_then(|s| {
+                serde_json::from_str::<ArchiveReport>(&s)
+                    .context("Failed to parse archive report export")
+            }) {
+            Ok(report) => reports.push((date, report)),
+            Err(e) => warn!(
+                "Skipping unreadable archive report export {}: {}",
+                path.display(),
+                e
+            ),
+        }
+    }
+
+    reports.sort_by_key(|(date, _)| *date);
+    Ok(reports)
+}
+
+/// Send the digest email, spooling it for retry (via [`crate::email_spool::EmailSpool`]) like
+/// the per-run report if the SMTP send fails.
+pub fn send_archive_digest_email(
+    config: &crate::email_reporter::EmailConfig,
+    digest: &ArchiveDigest,
+) -> Result<()> {
+    use crate::email_spool::EmailSpool;
+    use lettre::message::{MultiPart, SinglePart};
+    use lettre::transport::smtp::authentication::Credentials;
+    use lettre::{Message, SmtpTransport, Transport};
+    use std::time::Duration;
+
+    let subject = format!(
+        "SOAR Archive Digest - {} to {}",
+        digest.window_start, digest.window_end
+    );
+
+    info!("Sending archive digest email to {}", config.to_address);
+
+    let email = Message::builder()
+        .from(config.from_address.parse()?)
+        .to(config.to_address.parse()?)
+        .subject(subject.clone())
+        .multipart(
+            MultiPart::alternative()
+                .singlepart(SinglePart::plain(digest.to_text()))
+                .singlepart(SinglePart::html(digest.to_html())),
+        )?;
+
+    let creds = Credentials::new(config.smtp_username.clone(), config.smtp_password.clone());
+    let mailer = SmtpTransport::relay(&config.smtp_server)?
+        .port(config.smtp_port)
+        .credentials(creds)
+        .timeout(Some(Duration::from_secs(30)))
+        .build();
+
+    match mailer.send(&email) {
+        Ok(_) => {
+            info!("Archive digest email sent successfully");
+            Ok(())
+        }
+        Err(e) => {
+            warn!(
+                "Failed to send archive digest email, spooling for retry: {}",
+                e
+            );
+            let spool = EmailSpool::from_env();
+            if let Err(spool_err) = spool.enqueue(
+                &email,
+                &config.from_address,
+                vec![config.to_address.clone()],
+                &subject,
+            ) {
+                tracing::error!("Failed to spool archive digest email: {}", spool_err);
+            }
+            Err(anyhow::anyhow!("Failed to send digest email: {}", e))
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use anyhow::Result;
 use chrono::{NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// Get the environment name for display purposes
@@ -21,7 +22,7 @@ fn get_staging_prefix() -> &'static str {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TableArchiveMetrics {
     pub table_name: String,
     pub rows_deleted: usize,
@@ -31,14 +32,14 @@ pub struct TableArchiveMetrics {
     pub oldest_remaining: Option<NaiveDate>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DailyCount {
     pub date: NaiveDate,
     pub count: i64,
     pub archived: bool, // true if this day was archived (pruned)
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ArchiveReport {
     pub total_duration_secs: f64,
     pub tables: Vec<TableArchiveMetrics>,
@@ -357,18 +358,160 @@ impl ArchiveReport {
 
         html
     }
+
+    /// Render the same summary, per-table metrics, and daily-count analytics as aligned
+    /// monospaced plain text, for the `text/plain` part of the report email.
+    pub fn to_text(&self) -> String {
+        let environment = get_environment_name();
+        let mut text = format!(
+            "SOAR Archive Report - {}\n\
+             ✓ SUCCESS\n\n\
+             Environment:           {}\n\
+             Total Duration:        {}\n\
+             Tables Processed:      {}\n\
+             Total Rows Archived:   {}\n",
+            environment,
+            environment,
+            Self::format_duration(self.total_duration_secs),
+            self.tables.len(),
+            Self::format_number(self.tables.iter().map(|t| t.rows_deleted).sum())
+        );
+
+        if let Some(count) = self.unreferenced_locations_7d {
+            text.push_str(&format!(
+                "Unreferenced Locations (last 7 days): {}\n",
+                Self::format_count(count)
+            ));
+        }
+
+        text.push_str(&format!(
+            "Time:                  {}\n",
+            chrono::Local::now().format("%Y-%m-%d %H:%M:%S")
+        ));
+
+        text.push_str("\nArchive Summary\n");
+        text.push_str(
+            "Table                 Rows Deleted    File Size    Duration    Oldest Remaining\n",
+        );
+        for table in &self.tables {
+            let oldest_str = table
+                .oldest_remaining
+                .map(|d| d.format("%Y-%m-%d %H:%M:%S").to_string())
+                .unwrap_or_else(|| "N/A".to_string());
+
+            text.push_str(&format!(
+                "{:<20}  {:<14}  {:<11}  {:<10}  {}\n",
+                table.table_name,
+                Self::format_number(table.rows_deleted),
+                Self::format_file_size(table.file_size_bytes),
+                Self::format_duration(table.duration_secs),
+                oldest_str,
+            ));
+        }
+
+        if !self.daily_counts.is_empty() {
+            text.push_str("\nAnalytics\n");
+
+            let mut all_dates = std::collections::HashSet::new();
+            for counts in self.daily_counts.values() {
+                for daily_count in counts {
+                    all_dates.insert(daily_count.date);
+                }
+            }
+            let mut dates: Vec<NaiveDate> = all_dates.into_iter().collect();
+            dates.sort();
+
+            text.push_str("Date        ");
+            for table in &self.tables {
+                text.push_str(&format!("{:>14}", table.table_name));
+            }
+            text.push('\n');
+
+            for date in dates {
+                text.push_str(&format!("{}  ", date.format("%Y-%m-%d")));
+                for table in &self.tables {
+                    let cell = self
+                        .daily_counts
+                        .get(&table.table_name)
+                        .and_then(|counts| counts.iter().find(|dc| dc.date == date))
+                        .map(|dc| {
+                            let marker = if dc.archived { "*" } else { "" };
+                            format!("{}{}", Self::format_count(dc.count), marker)
+                        })
+                        .unwrap_or_else(|| "-".to_string());
+                    text.push_str(&format!("{:>14}", cell));
+                }
+                text.push('\n');
+            }
+            text.push_str("\n(* = archived/pruned day)\n");
+        }
+
+        text.push_str("\nGenerated by SOAR Archive System\n");
+
+        text
+    }
+
+    /// Serialize the full report as pretty-printed JSON, for operators feeding archive
+    /// metrics into dashboards or diffing them across runs.
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).map_err(Into::into)
+    }
+
+    /// Render the per-table metrics as CSV, one row per archived table.
+    pub fn to_csv(&self) -> String {
+        fn escape(field: &str) -> String {
+            if field.contains(',') || field.contains('"') || field.contains('\n') {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.to_string()
+            }
+        }
+
+        let mut csv = String::from(
+            "table_name,rows_deleted,file_path,file_size_bytes,duration_secs,oldest_remaining\n",
+        );
+
+        for table in &self.tables {
+            let oldest_str = table
+                .oldest_remaining
+                .map(|d| d.format("%Y-%m-%d").to_string())
+                .unwrap_or_default();
+
+            csv.push_str(&format!(
+                "{},{},{},{},{},{}\n",
+                escape(&table.table_name),
+                table.rows_deleted,
+                escape(&table.file_path),
+                table.file_size_bytes,
+                table.duration_secs,
+                oldest_str
+            ));
+        }
+
+        csv
+    }
 }
 
+/// Send the archive report by email. If the SMTP send fails (e.g. a transient relay
+/// outage), the message is spooled to disk via [`EmailSpool`] for retry on a later archive
+/// run rather than being lost. Either way, the spool is swept first so any previously
+/// failed reports get another chance to go out alongside the new one.
 pub fn send_archive_email_report(
     config: &crate::email_reporter::EmailConfig,
     report: &ArchiveReport,
 ) -> Result<()> {
-    use lettre::message::header::ContentType;
+    use crate::email_spool::EmailSpool;
+    use lettre::message::{MultiPart, SinglePart};
     use lettre::transport::smtp::authentication::Credentials;
     use lettre::{Message, SmtpTransport, Transport};
     use std::time::Duration;
     use tracing::info;
 
+    let spool = EmailSpool::from_env();
+    if let Err(e) = spool.sweep(config) {
+        tracing::warn!("Failed to sweep email spool: {}", e);
+    }
+
     let staging_prefix = get_staging_prefix();
     let subject = format!(
         "{}✓ SOAR Archive Complete - {}",
@@ -376,16 +519,17 @@ pub fn send_archive_email_report(
         chrono::Local::now().format("%Y-%m-%d")
     );
 
-    let html_body = report.to_html();
-
     info!("Sending archive email report to {}", config.to_address);
 
     let email = Message::builder()
         .from(config.from_address.parse()?)
         .to(config.to_address.parse()?)
-        .subject(subject)
-        .header(ContentType::TEXT_HTML)
-        .body(html_body)?;
+        .subject(subject.clone())
+        .multipart(
+            MultiPart::alternative()
+                .singlepart(SinglePart::plain(report.to_text()))
+                .singlepart(SinglePart::html(report.to_html())),
+        )?;
 
     let creds = Credentials::new(config.smtp_username.clone(), config.smtp_password.clone());
 
@@ -401,7 +545,18 @@ pub fn send_archive_email_report(
             Ok(())
         }
         Err(e) => {
-            tracing::warn!("Failed to send archive email report: {}", e);
+            tracing::warn!(
+                "Failed to send archive email report, spooling for retry: {}",
+                e
+            );
+            if let Err(spool_err) = spool.enqueue(
+                &email,
+                &config.from_address,
+                vec![config.to_address.clone()],
+                &subject,
+            ) {
+                tracing::error!("Failed to spool archive email report: {}", spool_err);
+            }
             Err(anyhow::anyhow!("Failed to send email: {}", e))
         }
     }
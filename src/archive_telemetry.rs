@@ -0,0 +1,119 @@
+use anyhow::{Context, Result};
+use std::path::Path;
+use tracing::info;
+
+use crate::archive_email_reporter::ArchiveReport;
+
+/// Export per-table archive metrics for external monitoring.
+///
+/// When `SOAR_OTEL_ENDPOINT` (or the standard `OTEL_EXPORTER_OTLP_ENDPOINT`) is set, each
+/// table's metrics are shipped as an OTLP span through a new tracer built by
+/// [`crate::telemetry::init_tracer`] for the `archive` component. That exporter sends to
+/// `OTEL_EXPORTER_OTLP_ENDPOINT` (or its default); the value of `SOAR_OTEL_ENDPOINT` only
+/// selects this mode and is not used as the export address. Otherwise a Prometheus textfile-collector
+/// compatible file is written to `archive_dir` (or `ARCHIVE_METRICS_TEXTFILE_DIR` if set) so
+/// node_exporter can scrape it.
+pub fn export_archive_metrics(report: &ArchiveReport, archive_dir: &Path) -> Result<()> {
+    let otel_endpoint = std::env::var("SOAR_OTEL_ENDPOINT")
+        .or_else(|_| std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT"))
+        .ok();
+
+    match otel_endpoint {
+        Some(endpoint) => export_otlp_spans(report, &endpoint),
+        None => {
+            let textfile_dir = std::env::var("ARCHIVE_METRICS_TEXTFILE_DIR")
+                .map(std::path::PathBuf::from)
+                .unwrap_or_else(|_| archive_dir.to_path_buf());
+            write_prometheus_textfile(report, &textfile_dir)
+        }
+    }
+}
+
+fn export_otlp_spans(report: &ArchiveReport, endpoint: &str) -> Result<()> {
+    use opentelemetry::trace::Tracer;
+    use opentelemetry::KeyValue;
+
+    let env = std::env::var("SOAR_ENV").unwrap_or_else(|_| "development".to_string());
+    let tracer = crate::telemetry::init_tracer(&env, "archive", env!("CARGO_PKG_VERSION"))
+        .context("Failed to initialize OTLP tracer for archive metrics")?;
+
+    info!(
+        "Exporting archive run metrics as OTLP spans to {}",
+        endpoint
+    );
+
+    let run_span = tracer
+        .span_builder("archive_run")
+        .with_attributes(vec![KeyValue::new(
+            "duration_secs",
+            report.total_duration_secs,
+        )])
+        .start(&tracer);
+    drop(run_span);
+
+    for table in &report.tables {
+        let span = tracer
+            .span_builder("archive_table")
+            .with_attributes(vec![
+                KeyValue::new("table_name", table.table_name.clone()),
+                KeyValue::new("rows_deleted", table.rows_deleted as i64),
+                KeyValue::new("file_size_bytes", table.file_size_bytes as i64),
+                KeyValue::new("duration_secs", table.duration_secs),
+            ])
+            .start(&tracer);
+        drop(span);
+    }
+
+    Ok(())
+}
+
+/// Write one gauge per metric, labeled by table, in Prometheus exposition format.
+fn write_prometheus_textfile(report: &ArchiveReport, dir: &Path) -> Result<()> {
+    std::fs::create_dir_all(dir).context("Failed to create archive metrics textfile directory")?;
+
+    let mut out = String::new();
+    out.push_str(
+        "# HELP archive_table_rows_deleted Rows deleted from the table by this archive run\n",
+    );
+    out.push_str("# TYPE archive_table_rows_deleted gauge\n");
+    for table in &report.tables {
+        out.push_str(&format!(
+            "archive_table_rows_deleted{{table=\"{}\"}} {}\n",
+            table.table_name, table.rows_deleted
+        ));
+    }
+
+    out.push_str(
+        "# HELP archive_table_file_size_bytes Size of the archive file written for the table\n",
+    );
+    out.push_str("# TYPE archive_table_file_size_bytes gauge\n");
+    for table in &report.tables {
+        out.push_str(&format!(
+            "archive_table_file_size_bytes{{table=\"{}\"}} {}\n",
+            table.table_name, table.file_size_bytes
+        ));
+    }
+
+    out.push_str("# HELP archive_table_duration_secs Time spent archiving the table\n");
+    out.push_str("# TYPE archive_table_duration_secs gauge\n");
+    for table in &report.tables {
+        out.push_str(&format!(
+            "archive_table_duration_secs{{table=\"{}\"}} {}\n",
+            table.table_name, table.duration_secs
+        ));
+    }
+
+    out.push_str("# HELP archive_run_duration_secs Total wall-clock duration of the archive run\n");
+    out.push_str("# TYPE archive_run_duration_secs gauge\n");
+    out.push_str(&format!(
+        "archive_run_duration_secs {}\n",
+        report.total_duration_secs
+    ));
+
+    let path = dir.join("archive_metrics.prom");
+    std::fs::write(&path, out).context("Failed to write archive metrics textfile")?;
+
+    info!("Wrote Prometheus textfile metrics to {}", path.display());
+
+    Ok(())
+}
@@ -240,6 +240,46 @@ async fn fetch_and_aggregate_fixes(
     .await?
 }
 
+/// Rebuild the weekly/monthly coverage rollup tables from daily
+/// `receiver_coverage_h3` rows.
+///
+/// If start_date or end_date are None, defaults to the last 90 days
+/// (ending yesterday), which comfortably covers the `Weekly`/`Monthly`
+/// bins `CoverageRepository::get_coverage_in_bbox` would pick for a range
+/// that wide. Safe to re-run for an overlapping range since each bin is
+/// fully recomputed from daily data, not incrementally merged.
+pub async fn aggregate_coverage_rollups(
+    pool: PgPool,
+    start_date: Option<NaiveDate>,
+    end_date: Option<NaiveDate>,
+) -> Result<()> {
+    let end_date = end_date.unwrap_or_else(|| Utc::now().date_naive() - chrono::Duration::days(1));
+    let start_date = start_date.unwrap_or_else(|| end_date - chrono::Duration::days(90));
+
+    if start_date > end_date {
+        warn!(
+            "Start date {} is after end date {}, nothing to roll up",
+            start_date, end_date
+        );
+        return Ok(());
+    }
+
+    info!(
+        "Rebuilding coverage rollups from {} to {}",
+        start_date, end_date
+    );
+
+    let repo = CoverageRepository::new(pool);
+    let (weekly, monthly) = repo.rebuild_rollups(start_date, end_date).await?;
+
+    info!(
+        "Coverage rollup rebuild complete: {} weekly rows, {} monthly rows",
+        weekly, monthly
+    );
+
+    Ok(())
+}
+
 /// Find the most recent date in the receiver_coverage_h3 table
 async fn find_last_coverage_date(pool: PgPool) -> Result<Option<NaiveDate>> {
     use soar::schema::receiver_coverage_h3;
@@ -13,7 +13,7 @@ use soar::flights::FlightModel;
 use soar::receiver_statuses::ReceiverStatus;
 use std::fs;
 use std::path::Path;
-use tracing::info;
+use tracing::{info, Instrument};
 
 /// Handle the archive command
 /// Archives data in the correct order to respect foreign key constraints:
@@ -95,6 +95,10 @@ pub async fn handle_archive(
     let pool_clone4 = pool.clone();
 
     // Archive all tables in parallel
+    // Each table's archive work runs inside its own `archive_table` span carrying
+    // `table_name`/`rows_deleted`/`file_size_bytes`/`duration_secs` fields (the latter three
+    // backfilled via `Span::record` once known), so runs are individually identifiable in
+    // structured log/trace output.
     let (flights_result, fixes_result, receiver_statuses_result, aprs_messages_result) = tokio::join!(
         async {
             let start = Instant::now();
@@ -107,8 +111,21 @@ pub async fn handle_archive(
                         return Err(anyhow::anyhow!("Failed to archive flights: {}", e));
                     }
                 };
-            Ok((metrics, start.elapsed().as_secs_f64()))
-        },
+            let duration = start.elapsed().as_secs_f64();
+            let file_size_bytes: u64 = metrics.archive_files.iter().map(|f| f.size_bytes).sum();
+            let span = tracing::Span::current();
+            span.record("rows_deleted", metrics.total_rows_deleted);
+            span.record("file_size_bytes", file_size_bytes);
+            span.record("duration_secs", duration);
+            Ok((metrics, duration))
+        }
+        .instrument(tracing::info_span!(
+            "archive_table",
+            table_name = "flights",
+            rows_deleted = tracing::field::Empty,
+            file_size_bytes = tracing::field::Empty,
+            duration_secs = tracing::field::Empty
+        )),
         async {
             let start = Instant::now();
             let metrics = match archive::<Fix>(&pool_clone2, fixes_before, &archive_dir_path).await
@@ -119,8 +136,21 @@ pub async fn handle_archive(
                     return Err(anyhow::anyhow!("Failed to archive fixes: {}", e));
                 }
             };
-            Ok((metrics, start.elapsed().as_secs_f64()))
-        },
+            let duration = start.elapsed().as_secs_f64();
+            let file_size_bytes: u64 = metrics.archive_files.iter().map(|f| f.size_bytes).sum();
+            let span = tracing::Span::current();
+            span.record("rows_deleted", metrics.total_rows_deleted);
+            span.record("file_size_bytes", file_size_bytes);
+            span.record("duration_secs", duration);
+            Ok((metrics, duration))
+        }
+        .instrument(tracing::info_span!(
+            "archive_table",
+            table_name = "fixes",
+            rows_deleted = tracing::field::Empty,
+            file_size_bytes = tracing::field::Empty,
+            duration_secs = tracing::field::Empty
+        )),
         async {
             let start = Instant::now();
             let metrics = match archive::<ReceiverStatus>(
@@ -139,8 +169,21 @@ pub async fn handle_archive(
                     ));
                 }
             };
-            Ok((metrics, start.elapsed().as_secs_f64()))
-        },
+            let duration = start.elapsed().as_secs_f64();
+            let file_size_bytes: u64 = metrics.archive_files.iter().map(|f| f.size_bytes).sum();
+            let span = tracing::Span::current();
+            span.record("rows_deleted", metrics.total_rows_deleted);
+            span.record("file_size_bytes", file_size_bytes);
+            span.record("duration_secs", duration);
+            Ok((metrics, duration))
+        }
+        .instrument(tracing::info_span!(
+            "archive_table",
+            table_name = "receiver_statuses",
+            rows_deleted = tracing::field::Empty,
+            file_size_bytes = tracing::field::Empty,
+            duration_secs = tracing::field::Empty
+        )),
         async {
             let start = Instant::now();
             let metrics =
@@ -153,8 +196,21 @@ pub async fn handle_archive(
                         return Err(anyhow::anyhow!("Failed to archive aprs_messages: {}", e));
                     }
                 };
-            Ok((metrics, start.elapsed().as_secs_f64()))
+            let duration = start.elapsed().as_secs_f64();
+            let file_size_bytes: u64 = metrics.archive_files.iter().map(|f| f.size_bytes).sum();
+            let span = tracing::Span::current();
+            span.record("rows_deleted", metrics.total_rows_deleted);
+            span.record("file_size_bytes", file_size_bytes);
+            span.record("duration_secs", duration);
+            Ok((metrics, duration))
         }
+        .instrument(tracing::info_span!(
+            "archive_table",
+            table_name = "aprs_messages",
+            rows_deleted = tracing::field::Empty,
+            file_size_bytes = tracing::field::Empty,
+            duration_secs = tracing::field::Empty
+        ))
     );
 
     // Check if any archival task failed
@@ -350,6 +406,38 @@ pub async fn handle_archive(
 
     info!("Archive process completed successfully");
 
+    // Write machine-readable exports (JSON + CSV) next to the archive output, if enabled
+    if std::env::var("ARCHIVE_EXPORT_METRICS").as_deref() == Ok("true") {
+        let today_str = today.format("%Y%m%d").to_string();
+
+        let json_path = archive_dir.join(format!("{}-archive_report.json", today_str));
+        match report.to_json() {
+            Ok(json) => {
+                if let Err(e) = fs::write(&json_path, json) {
+                    tracing::warn!("Failed to write archive report JSON export: {}", e);
+                }
+            }
+            Err(e) => tracing::warn!("Failed to serialize archive report to JSON: {}", e),
+        }
+
+        let csv_path = archive_dir.join(format!("{}-archive_report.csv", today_str));
+        if let Err(e) = fs::write(&csv_path, report.to_csv()) {
+            tracing::warn!("Failed to write archive report CSV export: {}", e);
+        }
+
+        info!(
+            "Wrote archive report exports: {} and {}",
+            json_path.display(),
+            csv_path.display()
+        );
+    }
+
+    // Export observability metrics: OTLP spans if SOAR_OTEL_ENDPOINT is configured,
+    // otherwise a Prometheus textfile for node_exporter.
+    if let Err(e) = soar::archive_telemetry::export_archive_metrics(&report, archive_dir) {
+        tracing::warn!("Failed to export archive observability metrics: {}", e);
+    }
+
     // Send email report
     match EmailConfig::from_env() {
         Ok(email_config) => {
@@ -369,6 +457,72 @@ pub async fn handle_archive(
     Ok(())
 }
 
+/// Marker file recording the last time a digest was actually emailed, so repeated invocations
+/// of this command (e.g. once per archive run) only send at the configured cadence.
+const DIGEST_MARKER_FILE: &str = ".last_digest_sent";
+
+/// Handle the archive-digest command
+///
+/// Loads the `*-archive_report.json` exports written by `archive` (when
+/// `ARCHIVE_EXPORT_METRICS=true`) for the trailing `window_days`, builds an aggregate digest,
+/// and emails it - but only if at least `ARCHIVE_DIGEST_CADENCE_DAYS` (default 7) have passed
+/// since the last digest was sent, so this can safely be invoked on the same cadence as
+/// `archive` itself without spamming a digest every run.
+pub async fn handle_archive_digest(archive_path: String, window_days: i64) -> Result<()> {
+    use soar::archive_digest::{ArchiveDigest, load_recent_reports, send_archive_digest_email};
+    use soar::email_reporter::EmailConfig;
+
+    let archive_dir = Path::new(&archive_path);
+    let marker_path = archive_dir.join(DIGEST_MARKER_FILE);
+    let cadence_days: i64 = std::env::var("ARCHIVE_DIGEST_CADENCE_DAYS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(7);
+
+    let today = Utc::now().date_naive();
+    if let Ok(contents) = fs::read_to_string(&marker_path)
+        && let Ok(last_sent) = NaiveDate::parse_from_str(contents.trim(), "%Y-%m-%d")
+        && (today - last_sent).num_days() < cadence_days
+    {
+        info!(
+            "Skipping archive digest: last sent {} ({} day cadence)",
+            last_sent, cadence_days
+        );
+        return Ok(());
+    }
+
+    let reports = load_recent_reports(archive_dir, window_days)?;
+    if reports.is_empty() {
+        info!(
+            "No archive report exports found in {} for the trailing {} days, skipping digest",
+            archive_path, window_days
+        );
+        return Ok(());
+    }
+
+    let digest = ArchiveDigest::from_reports(&reports);
+
+    match EmailConfig::from_env() {
+        Ok(email_config) => {
+            info!("Sending archive digest email...");
+            if let Err(e) = send_archive_digest_email(&email_config, &digest) {
+                tracing::warn!("Failed to send archive digest email: {}", e);
+                return Ok(());
+            }
+            fs::write(&marker_path, today.format("%Y-%m-%d").to_string())
+                .context("Failed to write digest marker file")?;
+        }
+        Err(e) => {
+            tracing::warn!(
+                "Email configuration not available, skipping digest email: {}",
+                e
+            );
+        }
+    }
+
+    Ok(())
+}
+
 /// Handle the resurrect command
 /// Resurrects (restores) archived data from compressed CSV files back into the database
 /// Restores data in the reverse order of archival to respect foreign key constraints:
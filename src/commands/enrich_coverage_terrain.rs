@@ -0,0 +1,56 @@
+use anyhow::Result;
+use tracing::info;
+
+use soar::coverage_repo::CoverageRepository;
+use soar::elevation::ElevationDB;
+use soar::terrain::{
+    ReferenceDataset, TerrainLookup, TerrainManifestRepository, refresh_reference_data,
+};
+use soar::web::PgPool;
+
+/// One-shot terrain enrichment: optionally refresh the land-cover reference
+/// data set, then drain `CoverageRepository::enrich_terrain`'s backlog of
+/// un-enriched coverage hexes. Ground elevation always comes from the
+/// existing `ElevationService` (`ELEVATION_DATA_PATH`/`ELEVATION_S3_BUCKET`),
+/// so `landcover_url` only affects the `terrain_class` column; omit it to
+/// backfill ground elevation alone.
+///
+/// Safe to re-run: already-enriched hexes are never re-selected, and the
+/// reference-data refresh is a no-op if the remote file hasn't changed since
+/// it was last loaded (see `TerrainManifestRepository`).
+pub async fn enrich_coverage_terrain(pool: PgPool, landcover_url: Option<String>) -> Result<()> {
+    let elevation = ElevationDB::new()?;
+    let terrain_lookup = TerrainLookup::new();
+
+    if let Some(url) = landcover_url {
+        let manifest = TerrainManifestRepository::new(pool.clone());
+        let dataset = ReferenceDataset {
+            name: "landcover",
+            url,
+        };
+
+        refresh_reference_data(&manifest, &terrain_lookup, &dataset).await?;
+    } else {
+        info!("No land-cover URL given, backfilling ground elevation only");
+    }
+
+    let repo = CoverageRepository::new(pool);
+    let mut total = 0usize;
+
+    loop {
+        let count = repo.enrich_terrain(&elevation, &terrain_lookup).await?;
+        if count == 0 {
+            break;
+        }
+
+        total += count;
+        info!("Terrain enrichment: tagged {} coverage hexes so far", total);
+    }
+
+    info!(
+        "Terrain enrichment complete: {} coverage hexes tagged",
+        total
+    );
+
+    Ok(())
+}
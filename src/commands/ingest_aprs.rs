@@ -1,9 +1,82 @@
 use anyhow::{Context, Result};
+use rand::Rng;
 use soar::aprs_client::{AprsClient, AprsClientConfigBuilder};
 use soar::instance_lock::InstanceLock;
 use std::env;
+use std::time::Duration;
 use tracing::Instrument;
-use tracing::{error, info};
+use tracing::{error, info, warn};
+
+/// Default base delay (seconds) for the NATS reconnect loop's exponential backoff.
+/// Overridable via the `NATS_RECONNECT_BASE_DELAY_SECS` env var.
+const DEFAULT_NATS_RECONNECT_BASE_DELAY_SECS: u64 = 1;
+
+/// Default ceiling (seconds) on the NATS reconnect backoff delay.
+/// Overridable via the `NATS_RECONNECT_MAX_DELAY_SECS` env var.
+const DEFAULT_NATS_RECONNECT_MAX_DELAY_SECS: u64 = 60;
+
+/// Default number of consecutive failures before the circuit breaker opens.
+/// Overridable via the `NATS_RECONNECT_CIRCUIT_THRESHOLD` env var.
+const DEFAULT_NATS_RECONNECT_CIRCUIT_THRESHOLD: u32 = 10;
+
+fn env_u64(name: &str, default: u64) -> u64 {
+    env::var(name)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
+
+fn env_u32(name: &str, default: u32) -> u32 {
+    env::var(name)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
+
+/// Compute `min(base * 2^failures, max)`, jittered by a uniform random factor
+/// in `[0.5, 1.0]` so a fleet of ingesters that fail together don't all
+/// reconnect in lockstep.
+fn nats_reconnect_backoff(failures: u32, base_delay: Duration, max_delay: Duration) -> Duration {
+    let exp = base_delay
+        .saturating_mul(1 << failures.min(20))
+        .min(max_delay);
+    let jitter: f64 = rand::rng().random_range(0.5..=1.0);
+    Duration::from_millis((exp.as_millis() as f64 * jitter) as u64)
+}
+
+/// Opt-in tokio-console runtime introspection, gated behind the
+/// `ENABLE_TOKIO_CONSOLE` env var so there's no instrumentation overhead
+/// when it's unset. Lets maintainers watch per-task poll times and wakeups
+/// for the tasks this service spawns (e.g. `aprs-ingest-loop`,
+/// `metrics-server`) and whether the blocking pool backing the Diesel
+/// repositories is saturated.
+fn maybe_init_tokio_console() {
+    use tracing_subscriber::layer::SubscriberExt;
+    use tracing_subscriber::util::SubscriberInitExt;
+
+    let enabled = env::var("ENABLE_TOKIO_CONSOLE")
+        .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+        .unwrap_or(false);
+    if !enabled {
+        return;
+    }
+
+    let console_layer = console_subscriber::ConsoleLayer::builder()
+        .server_addr(([0, 0, 0, 0], 6671))
+        .spawn();
+
+    if tracing_subscriber::registry()
+        .with(console_layer)
+        .try_init()
+        .is_ok()
+    {
+        info!(
+            "tokio-console subscriber initialized on port 6671 - connect with `tokio-console http://localhost:6671`"
+        );
+    } else {
+        warn!("ENABLE_TOKIO_CONSOLE is set but a global tracing subscriber is already installed");
+    }
+}
 
 pub async fn handle_ingest_aprs(
     server: String,
@@ -18,6 +91,8 @@ pub async fn handle_ingest_aprs(
         scope.set_tag("operation", "ingest-aprs");
     });
 
+    maybe_init_tokio_console();
+
     // Automatically switch to port 10152 for full feed if no filter specified
     // Port 14580 requires a filter, port 10152 provides the full global feed
     if filter.is_none() && port == 14580 {
@@ -79,7 +154,7 @@ pub async fn handle_ingest_aprs(
             async move {
                 soar::metrics::start_metrics_server(metrics_port).await;
             }
-            .instrument(tracing::info_span!("metrics_server")),
+            .instrument(tracing::info_span!("metrics-server")),
         );
     }
 
@@ -98,42 +173,45 @@ pub async fn handle_ingest_aprs(
     let (shutdown_tx, mut shutdown_rx) = tokio::sync::oneshot::channel::<()>();
 
     // Spawn signal handler task for both SIGINT and SIGTERM
-    tokio::spawn(async move {
-        #[cfg(unix)]
-        {
-            use tokio::signal::unix::{SignalKind, signal};
-
-            let mut sigterm =
-                signal(SignalKind::terminate()).expect("Failed to register SIGTERM handler");
-            let mut sigint =
-                signal(SignalKind::interrupt()).expect("Failed to register SIGINT handler");
-
-            tokio::select! {
-                _ = sigterm.recv() => {
-                    info!("Received SIGTERM, exiting immediately...");
-                }
-                _ = sigint.recv() => {
-                    info!("Received SIGINT (Ctrl+C), exiting immediately...");
+    tokio::spawn(
+        async move {
+            #[cfg(unix)]
+            {
+                use tokio::signal::unix::{signal, SignalKind};
+
+                let mut sigterm =
+                    signal(SignalKind::terminate()).expect("Failed to register SIGTERM handler");
+                let mut sigint =
+                    signal(SignalKind::interrupt()).expect("Failed to register SIGINT handler");
+
+                tokio::select! {
+                    _ = sigterm.recv() => {
+                        info!("Received SIGTERM, exiting immediately...");
+                    }
+                    _ = sigint.recv() => {
+                        info!("Received SIGINT (Ctrl+C), exiting immediately...");
+                    }
                 }
             }
-        }
 
-        #[cfg(not(unix))]
-        {
-            match tokio::signal::ctrl_c().await {
-                Ok(()) => {
-                    info!("Received SIGINT (Ctrl+C), exiting immediately...");
-                }
-                Err(err) => {
-                    error!("Failed to listen for SIGINT signal: {}", err);
-                    return;
+            #[cfg(not(unix))]
+            {
+                match tokio::signal::ctrl_c().await {
+                    Ok(()) => {
+                        info!("Received SIGINT (Ctrl+C), exiting immediately...");
+                    }
+                    Err(err) => {
+                        error!("Failed to listen for SIGINT signal: {}", err);
+                        return;
+                    }
                 }
             }
-        }
 
-        // Signal shutdown
-        let _ = shutdown_tx.send(());
-    });
+            // Signal shutdown
+            let _ = shutdown_tx.send(());
+        }
+        .instrument(tracing::info_span!("aprs-shutdown-signal-handler")),
+    );
 
     // Create APRS client config
     let config = AprsClientConfigBuilder::new()
@@ -145,76 +223,182 @@ pub async fn handle_ingest_aprs(
         .retry_delay_seconds(retry_delay)
         .build();
 
-    // Retry loop for JetStream connection and APRS ingestion
-    loop {
-        // Check if shutdown was requested
-        if shutdown_rx.try_recv().is_ok() {
-            info!("Shutdown requested, exiting...");
-            std::process::exit(0);
-        }
+    // Backoff/circuit-breaker configuration for the NATS connection loop below
+    let nats_base_delay = Duration::from_secs(env_u64(
+        "NATS_RECONNECT_BASE_DELAY_SECS",
+        DEFAULT_NATS_RECONNECT_BASE_DELAY_SECS,
+    ));
+    let nats_max_delay = Duration::from_secs(env_u64(
+        "NATS_RECONNECT_MAX_DELAY_SECS",
+        DEFAULT_NATS_RECONNECT_MAX_DELAY_SECS,
+    ));
+    let nats_circuit_threshold = env_u32(
+        "NATS_RECONNECT_CIRCUIT_THRESHOLD",
+        DEFAULT_NATS_RECONNECT_CIRCUIT_THRESHOLD,
+    );
+    let mut nats_consecutive_failures: u32 = 0;
 
-        info!("Connecting to NATS at {}...", nats_url);
-        let nats_client_name = if std::env::var("SOAR_ENV") == Ok("production".into()) {
-            "soar-aprs-ingester"
-        } else {
-            "soar-aprs-ingester-staging"
-        };
-        let nats_result = async_nats::ConnectOptions::new()
-            .name(nats_client_name)
-            .client_capacity(65536) // Increase from default 2048 to prevent blocking on publish
-            .subscription_capacity(1024 * 128) // Increase subscription buffer
-            .connect(&nats_url)
-            .await;
-
-        let nats_client = match nats_result {
-            Ok(client) => {
-                info!("Connected to NATS successfully");
-                client
-            }
-            Err(e) => {
-                error!("Failed to connect to NATS: {} - retrying in 1s", e);
-                metrics::counter!("aprs.jetstream.connection_failed").increment(1);
-                tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
-                continue;
-            }
-        };
+    // Retry loop for JetStream connection and APRS ingestion, run as its own
+    // named task (`aprs-ingest-loop`) so it's identifiable in `tokio-console`
+    // separately from whichever task called `handle_ingest_aprs`.
+    let ingest_loop = tokio::spawn(
+        async move {
+            loop {
+                // Check if shutdown was requested
+                if shutdown_rx.try_recv().is_ok() {
+                    info!("Shutdown requested, exiting...");
+                    std::process::exit(0);
+                }
 
-        info!("NATS ready - will publish to subject '{}'", nats_subject);
+                info!("Connecting to NATS at {}...", nats_url);
+                let nats_client_name = if std::env::var("SOAR_ENV") == Ok("production".into()) {
+                    "soar-aprs-ingester"
+                } else {
+                    "soar-aprs-ingester-staging"
+                };
+                let nats_result = async_nats::ConnectOptions::new()
+                    .name(nats_client_name)
+                    .client_capacity(65536) // Increase from default 2048 to prevent blocking on publish
+                    .subscription_capacity(1024 * 128) // Increase subscription buffer
+                    .connect(&nats_url)
+                    .await;
 
-        // Create NATS publisher for raw APRS messages
-        let nats_publisher =
-            soar::aprs_nats_publisher::NatsPublisher::new(nats_client, nats_subject.to_string());
+                let nats_client = match nats_result {
+                    Ok(client) => {
+                        info!("Connected to NATS successfully");
+                        client
+                    }
+                    Err(e) => {
+                        nats_consecutive_failures += 1;
+                        let delay = nats_reconnect_backoff(
+                            nats_consecutive_failures,
+                            nats_base_delay,
+                            nats_max_delay,
+                        );
 
-        let mut client = AprsClient::new(config.clone());
+                        report_nats_backoff(
+                            &health_state,
+                            nats_consecutive_failures,
+                            nats_circuit_threshold,
+                            delay,
+                        )
+                        .await;
 
-        // Mark NATS as connected in health state
-        {
-            let mut health = health_state.write().await;
-            health.jetstream_connected = true; // Keep same field name for now
-        }
+                        error!(
+                    "Failed to connect to NATS: {} - retrying in {:.1}s (consecutive failures: {})",
+                    e,
+                    delay.as_secs_f64(),
+                    nats_consecutive_failures
+                );
+                        metrics::counter!("aprs.jetstream.connection_failed").increment(1);
+                        tokio::time::sleep(delay).await;
+                        continue;
+                    }
+                };
 
-        info!("Starting APRS client for ingestion...");
+                info!("NATS ready - will publish to subject '{}'", nats_subject);
 
-        // Run APRS client - this will block until failure or shutdown
-        match client.start_jetstream(nats_publisher).await {
-            Ok(_) => {
-                info!("APRS ingestion stopped normally");
-                break;
-            }
-            Err(e) => {
-                error!("APRS ingestion failed: {} - retrying in 1s", e);
-                metrics::counter!("aprs.ingest_failed").increment(1);
+                // Create NATS publisher for raw APRS messages
+                let nats_publisher = soar::aprs_nats_publisher::NatsPublisher::new(
+                    nats_client,
+                    nats_subject.to_string(),
+                );
 
-                // Mark NATS as disconnected
+                let mut client = AprsClient::new(config.clone());
+
+                // Successful connect - reset the backoff/circuit-breaker state
+                nats_consecutive_failures = 0;
                 {
                     let mut health = health_state.write().await;
-                    health.jetstream_connected = false; // Keep same field name for now
+                    health.jetstream_connected = true; // Keep same field name for now
+                    health.nats_consecutive_failures = 0;
+                    health.nats_backoff_ms = 0;
+                    health.nats_circuit_open = false;
                 }
+                metrics::gauge!("aprs.nats.reconnect.consecutive_failures").set(0.0);
+                metrics::gauge!("aprs.nats.reconnect.backoff_ms").set(0.0);
+                metrics::gauge!("aprs.nats.reconnect.circuit_open").set(0.0);
+
+                info!("Starting APRS client for ingestion...");
+
+                // Run APRS client - this will block until failure or shutdown
+                match client.start_jetstream(nats_publisher).await {
+                    Ok(_) => {
+                        info!("APRS ingestion stopped normally");
+                        break;
+                    }
+                    Err(e) => {
+                        nats_consecutive_failures += 1;
+                        let delay = nats_reconnect_backoff(
+                            nats_consecutive_failures,
+                            nats_base_delay,
+                            nats_max_delay,
+                        );
+
+                        report_nats_backoff(
+                            &health_state,
+                            nats_consecutive_failures,
+                            nats_circuit_threshold,
+                            delay,
+                        )
+                        .await;
+
+                        error!(
+                    "APRS ingestion failed: {} - retrying in {:.1}s (consecutive failures: {})",
+                    e,
+                    delay.as_secs_f64(),
+                    nats_consecutive_failures
+                );
+                        metrics::counter!("aprs.ingest_failed").increment(1);
 
-                tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+                        // Mark NATS as disconnected
+                        {
+                            let mut health = health_state.write().await;
+                            health.jetstream_connected = false; // Keep same field name for now
+                        }
+
+                        tokio::time::sleep(delay).await;
+                    }
+                }
             }
         }
-    }
+        .instrument(tracing::info_span!("aprs-ingest-loop")),
+    );
+
+    ingest_loop
+        .await
+        .context("APRS ingest loop task panicked")?;
 
     Ok(())
 }
+
+/// Record the current backoff/circuit-breaker state in both the health state
+/// (consumed by the `/health` endpoint) and as gauges (scraped by Prometheus),
+/// logging a distinct warning once the circuit breaker opens.
+async fn report_nats_backoff(
+    health_state: &std::sync::Arc<tokio::sync::RwLock<soar::metrics::AprsIngestHealth>>,
+    consecutive_failures: u32,
+    circuit_threshold: u32,
+    delay: Duration,
+) {
+    let circuit_open = consecutive_failures >= circuit_threshold;
+
+    {
+        let mut health = health_state.write().await;
+        health.nats_consecutive_failures = consecutive_failures;
+        health.nats_backoff_ms = delay.as_millis() as u64;
+        health.nats_circuit_open = circuit_open;
+    }
+
+    metrics::gauge!("aprs.nats.reconnect.consecutive_failures").set(consecutive_failures as f64);
+    metrics::gauge!("aprs.nats.reconnect.backoff_ms").set(delay.as_millis() as f64);
+    metrics::gauge!("aprs.nats.reconnect.circuit_open").set(if circuit_open { 1.0 } else { 0.0 });
+
+    if circuit_open {
+        warn!(
+            "Circuit open: {} consecutive NATS connection failures, backing off at max delay ({:.1}s)",
+            consecutive_failures,
+            delay.as_secs_f64()
+        );
+    }
+}
@@ -1,6 +1,8 @@
+pub mod aggregate_coverage;
 pub mod archive;
 pub mod consume_beast;
 pub mod dump_unified_ddb;
+pub mod enrich_coverage_terrain;
 pub mod ingest_aprs;
 pub mod ingest_beast;
 pub mod load_data;
@@ -8,7 +10,9 @@ pub mod pull_data;
 pub mod run;
 pub mod sitemap;
 
-pub use archive::{handle_archive, handle_resurrect};
+pub use aggregate_coverage::{aggregate_coverage, aggregate_coverage_rollups};
+pub use archive::{handle_archive, handle_archive_digest, handle_resurrect};
+pub use enrich_coverage_terrain::enrich_coverage_terrain;
 #[allow(unused_imports)] // Will be used in future commits
 pub use consume_beast::handle_consume_beast;
 pub use dump_unified_ddb::handle_dump_unified_ddb;
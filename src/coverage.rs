@@ -1,6 +1,6 @@
 use chrono::{DateTime, NaiveDate, Utc};
 use diesel::prelude::*;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 /// Diesel model for receiver_coverage_h3 table
@@ -18,6 +18,14 @@ pub struct ReceiverCoverageH3 {
     pub min_altitude_msl_feet: Option<i32>,
     pub max_altitude_msl_feet: Option<i32>,
     pub avg_altitude_msl_feet: Option<i32>,
+    /// Ground elevation at the hex's centroid, backfilled by
+    /// `CoverageRepository::enrich_terrain` from the existing elevation
+    /// service. `None` until enrichment has run for this hex.
+    pub ground_elevation_msl_feet: Option<i32>,
+    /// Coarse land-cover classification at the hex's centroid, backfilled
+    /// alongside `ground_elevation_msl_feet` from the downloaded land-cover
+    /// reference data set (see [`crate::terrain`]).
+    pub terrain_class: Option<i16>,
     pub updated_at: DateTime<Utc>,
 }
 
@@ -37,6 +45,39 @@ pub struct NewReceiverCoverageH3 {
     pub avg_altitude_msl_feet: Option<i32>,
 }
 
+/// Time-bin granularity for coverage queries. Wide bbox queries roll up to
+/// a coarser bin so they read one row per hex per bucket instead of one
+/// per hex per day; see `CoverageRepository::bin_for_range` for the
+/// automatic selection and the `add_coverage_rollup_tables` migration for
+/// the backing tables.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CoverageBin {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+impl CoverageBin {
+    /// Table storing coverage rows at this granularity.
+    pub fn table_name(self) -> &'static str {
+        match self {
+            CoverageBin::Daily => "receiver_coverage_h3",
+            CoverageBin::Weekly => "receiver_coverage_h3_weekly",
+            CoverageBin::Monthly => "receiver_coverage_h3_monthly",
+        }
+    }
+
+    /// Date column at this granularity ("date" for daily rows, "bin_start"
+    /// for the weekly/monthly rollups).
+    pub fn date_column(self) -> &'static str {
+        match self {
+            CoverageBin::Daily => "date",
+            CoverageBin::Weekly | CoverageBin::Monthly => "bin_start",
+        }
+    }
+}
+
 /// GeoJSON Feature for H3 hex (API response)
 #[derive(Serialize, Debug, Clone)]
 pub struct CoverageHexFeature {
@@ -59,6 +100,34 @@ pub struct CoverageHexProperties {
     pub max_altitude_msl_feet: Option<i32>,
     pub avg_altitude_msl_feet: Option<i32>,
     pub coverage_hours: f64, // Hours between first and last seen
+    pub ground_elevation_msl_feet: Option<i32>,
+    pub terrain_class: Option<crate::terrain::TerrainClass>,
+    /// `min_altitude_msl_feet - ground_elevation_msl_feet`; how far above the
+    /// ground the lowest aircraft contact observed in this hex was. Lets
+    /// clients tell a true coverage gap from one explained by terrain
+    /// shadowing. `None` until terrain enrichment has run for this hex.
+    pub terrain_clearance_feet: Option<i32>,
+}
+
+/// Build the GeoJSON polygon geometry for an H3 cell.
+/// Note: GeoJSON uses [lng, lat] order, not [lat, lng].
+fn hex_polygon(cell: h3o::CellIndex) -> serde_json::Value {
+    let boundary = cell.boundary();
+
+    let mut coords: Vec<[f64; 2]> = boundary
+        .iter()
+        .map(|latlng| [latlng.lng(), latlng.lat()])
+        .collect();
+
+    // Close the polygon by adding the first point again
+    if let Some(first) = coords.first().copied() {
+        coords.push(first);
+    }
+
+    serde_json::json!({
+        "type": "Polygon",
+        "coordinates": [coords]
+    })
 }
 
 impl CoverageHexFeature {
@@ -68,30 +137,19 @@ impl CoverageHexFeature {
 
         // Convert BIGINT to H3 index
         let h3_index = CellIndex::try_from(coverage.h3_index as u64)?;
-
-        // Get hex boundary as lat/lng coordinates
-        let boundary = h3_index.boundary();
-
-        // Convert to GeoJSON polygon
-        // Note: GeoJSON uses [lng, lat] order, not [lat, lng]
-        let mut coords: Vec<[f64; 2]> = boundary
-            .iter()
-            .map(|latlng| [latlng.lng(), latlng.lat()])
-            .collect();
-
-        // Close the polygon by adding the first point again
-        if let Some(first) = coords.first().copied() {
-            coords.push(first);
-        }
-
-        let geometry = serde_json::json!({
-            "type": "Polygon",
-            "coordinates": [coords]
-        });
+        let geometry = hex_polygon(h3_index);
 
         let coverage_hours =
             (coverage.last_seen_at - coverage.first_seen_at).num_seconds() as f64 / 3600.0;
 
+        let terrain_clearance_feet = match (
+            coverage.min_altitude_msl_feet,
+            coverage.ground_elevation_msl_feet,
+        ) {
+            (Some(min_alt), Some(ground)) => Some(min_alt - ground),
+            _ => None,
+        };
+
         Ok(Self {
             feature_type: "Feature".to_string(),
             geometry,
@@ -106,7 +164,89 @@ impl CoverageHexFeature {
                 max_altitude_msl_feet: coverage.max_altitude_msl_feet,
                 avg_altitude_msl_feet: coverage.avg_altitude_msl_feet,
                 coverage_hours,
+                ground_elevation_msl_feet: coverage.ground_elevation_msl_feet,
+                terrain_class: coverage
+                    .terrain_class
+                    .and_then(crate::terrain::TerrainClass::from_i16),
+                terrain_clearance_feet,
+            },
+        })
+    }
+
+    /// Convert a live `coverage_updates` NOTIFY payload (see
+    /// [`crate::coverage_stream`]) to the same GeoJSON shape as
+    /// [`Self::from_coverage`]. The trigger payload only carries h3_index,
+    /// resolution, receiver_id, date and fix_count, so seen timestamps are both set to now (giving zero
+    /// `coverage_hours`) and altitude fields are left unset; clients that
+    /// need those should still pull `get_coverage_geojson` on load.
+    pub fn from_notification(notification: &CoverageNotification) -> anyhow::Result<Self> {
+        use h3o::CellIndex;
+
+        let h3_index = CellIndex::try_from(notification.h3_index as u64)?;
+        let geometry = hex_polygon(h3_index);
+        let now = Utc::now();
+
+        Ok(Self {
+            feature_type: "Feature".to_string(),
+            geometry,
+            properties: CoverageHexProperties {
+                h3_index: h3_index.to_string(),
+                resolution: notification.resolution,
+                receiver_id: notification.receiver_id,
+                fix_count: notification.fix_count,
+                first_seen_at: now,
+                last_seen_at: now,
+                min_altitude_msl_feet: None,
+                max_altitude_msl_feet: None,
+                avg_altitude_msl_feet: None,
+                coverage_hours: 0.0,
+                ground_elevation_msl_feet: None,
+                terrain_class: None,
+                terrain_clearance_feet: None,
             },
         })
     }
 }
+
+/// Payload of the `coverage_updates` Postgres NOTIFY channel, fired by a
+/// trigger on `receiver_coverage_h3` for every insert/update (see the
+/// `add_coverage_updates_trigger` migration). Carries just enough to locate
+/// the hex and its latest count — see [`crate::coverage_stream`] for the
+/// listener that turns these into a live SSE feed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoverageNotification {
+    pub h3_index: i64,
+    pub resolution: i16,
+    pub receiver_id: Uuid,
+    pub date: NaiveDate,
+    pub fix_count: i32,
+}
+
+impl CoverageNotification {
+    /// Approximate center of the hex, used for bounding-box filtering of
+    /// live updates. Averaging the boundary vertices is close enough for
+    /// this purpose; exact centroid isn't needed.
+    pub fn centroid(&self) -> anyhow::Result<(f64, f64)> {
+        h3_centroid(self.h3_index)
+    }
+}
+
+/// Approximate (lat, lng) center of an H3 cell, found by averaging its
+/// boundary vertices. Shared by [`CoverageNotification::centroid`] and the
+/// terrain enrichment in [`crate::terrain`], where an exact centroid isn't
+/// needed either.
+pub fn h3_centroid(h3_index: i64) -> anyhow::Result<(f64, f64)> {
+    use h3o::CellIndex;
+
+    let cell = CellIndex::try_from(h3_index as u64)?;
+    let boundary = cell.boundary();
+
+    let (mut lat_sum, mut lng_sum, mut count) = (0.0, 0.0, 0.0_f64);
+    for latlng in boundary.iter() {
+        lat_sum += latlng.lat();
+        lng_sum += latlng.lng();
+        count += 1.0;
+    }
+
+    Ok((lat_sum / count, lng_sum / count))
+}
@@ -5,8 +5,9 @@ use chrono::NaiveDate;
 use moka::future::Cache;
 use uuid::Uuid;
 
-use crate::coverage::CoverageHexFeature;
+use crate::coverage::{CoverageBin, CoverageHexFeature};
 use crate::coverage_repo::CoverageRepository;
+use crate::terrain::TerrainClass;
 
 /// Cache key for coverage queries
 #[derive(Clone, Debug, Hash, Eq, PartialEq)]
@@ -22,6 +23,9 @@ struct CoverageKey {
     min_altitude: Option<i32>,
     max_altitude: Option<i32>,
     limit: i64,
+    bin: Option<CoverageBin>,
+    terrain_class: Option<TerrainClass>,
+    max_terrain_clearance_feet: Option<i32>,
 }
 
 impl CoverageKey {
@@ -38,6 +42,9 @@ impl CoverageKey {
         min_altitude: Option<i32>,
         max_altitude: Option<i32>,
         limit: i64,
+        bin: Option<CoverageBin>,
+        terrain_class: Option<TerrainClass>,
+        max_terrain_clearance_feet: Option<i32>,
     ) -> Self {
         Self {
             resolution,
@@ -51,6 +58,9 @@ impl CoverageKey {
             min_altitude,
             max_altitude,
             limit,
+            bin,
+            terrain_class,
+            max_terrain_clearance_feet,
         }
     }
 }
@@ -90,6 +100,9 @@ impl CoverageCache {
         min_altitude: Option<i32>,
         max_altitude: Option<i32>,
         limit: i64,
+        bin: Option<CoverageBin>,
+        terrain_class: Option<TerrainClass>,
+        max_terrain_clearance_feet: Option<i32>,
     ) -> Result<Vec<CoverageHexFeature>> {
         let start = Instant::now();
         let key = CoverageKey::new(
@@ -104,6 +117,9 @@ impl CoverageCache {
             min_altitude,
             max_altitude,
             limit,
+            bin,
+            terrain_class,
+            max_terrain_clearance_feet,
         );
 
         if let Some(cached) = self.cache.get(&key).await {
@@ -128,6 +144,9 @@ impl CoverageCache {
                 min_altitude,
                 max_altitude,
                 limit,
+                bin,
+                terrain_class,
+                max_terrain_clearance_feet,
             )
             .await?;
 
@@ -1,11 +1,14 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use chrono::{DateTime, NaiveDate, Utc};
 use diesel::prelude::*;
 use diesel::sql_types;
-use tracing::info;
+use metrics::{counter, histogram};
+use tracing::{info, instrument};
 use uuid::Uuid;
 
-use crate::coverage::{CoverageHexFeature, NewReceiverCoverageH3, ReceiverCoverageH3};
+use crate::coverage::{CoverageBin, CoverageHexFeature, NewReceiverCoverageH3, ReceiverCoverageH3};
+use crate::elevation::ElevationService;
+use crate::terrain::TerrainLookup;
 use crate::web::PgPool;
 
 /// Queryable result for raw SQL coverage queries
@@ -31,6 +34,10 @@ struct CoverageQueryResult {
     max_altitude_msl_feet: Option<i32>,
     #[diesel(sql_type = sql_types::Nullable<sql_types::Integer>)]
     avg_altitude_msl_feet: Option<i32>,
+    #[diesel(sql_type = sql_types::Nullable<sql_types::Integer>)]
+    ground_elevation_msl_feet: Option<i32>,
+    #[diesel(sql_type = sql_types::Nullable<sql_types::SmallInt>)]
+    terrain_class: Option<i16>,
     #[diesel(sql_type = sql_types::Timestamptz)]
     updated_at: DateTime<Utc>,
 }
@@ -48,11 +55,107 @@ impl From<CoverageQueryResult> for ReceiverCoverageH3 {
             min_altitude_msl_feet: result.min_altitude_msl_feet,
             max_altitude_msl_feet: result.max_altitude_msl_feet,
             avg_altitude_msl_feet: result.avg_altitude_msl_feet,
+            ground_elevation_msl_feet: result.ground_elevation_msl_feet,
+            terrain_class: result.terrain_class,
             updated_at: result.updated_at,
         }
     }
 }
 
+/// Queryable result for the `ST_AsMVT` aggregate. Aggregating over zero
+/// matching rows still yields one row with a `NULL` tile, so the column is
+/// nullable and callers treat `None` as an empty (but valid) tile.
+#[derive(QueryableByName, Debug)]
+struct MvtQueryResult {
+    #[diesel(sql_type = sql_types::Nullable<sql_types::Binary>)]
+    mvt: Option<Vec<u8>>,
+}
+
+/// Map an XYZ tile zoom level to the coarsest stored H3 resolution that
+/// still looks reasonably detailed at that zoom (z 3 -> res 3, z 6 -> res 5,
+/// z 9 -> res 7, ...), clamped to the resolutions `aggregate_coverage`
+/// actually populates (3-8).
+fn zoom_to_resolution(z: u8) -> i16 {
+    let resolution = 3 + (z.saturating_sub(3) as i16 * 2) / 3;
+    resolution.clamp(3, 8)
+}
+
+/// Compute the WGS84 envelope (west, south, east, north) of an XYZ slippy
+/// map tile.
+fn tile_bounds(z: u8, x: u32, y: u32) -> (f64, f64, f64, f64) {
+    let tiles_per_axis = 2f64.powi(z as i32);
+
+    let west = x as f64 / tiles_per_axis * 360.0 - 180.0;
+    let east = (x as f64 + 1.0) / tiles_per_axis * 360.0 - 180.0;
+    let north = tile_y_to_lat(y, tiles_per_axis);
+    let south = tile_y_to_lat(y + 1, tiles_per_axis);
+
+    (west, south, east, north)
+}
+
+/// Pick the coarsest rollup bin whose rows still fully cover the requested
+/// range, so a bbox query only reads one row per hex per bucket instead of
+/// one per hex per day. Multi-month ranges roll up to `Monthly`,
+/// multi-week ranges to `Weekly`, and anything shorter stays `Daily` so
+/// recent, narrow queries keep per-day precision.
+fn bin_for_range(start_date: NaiveDate, end_date: NaiveDate) -> CoverageBin {
+    let span_days = (end_date - start_date).num_days();
+
+    if span_days > 180 {
+        CoverageBin::Monthly
+    } else if span_days > 21 {
+        CoverageBin::Weekly
+    } else {
+        CoverageBin::Daily
+    }
+}
+
+/// Structured errors from `CoverageRepository`'s data-access methods.
+///
+/// Unlike the repo's other raw-SQL repositories (which bubble up bare
+/// `anyhow::Error`), the bbox path handles untrusted query parameters on
+/// every request, so callers get enough context here (the bound values, the
+/// underlying diesel error) to triage a bad query without re-deriving it
+/// from log lines. `CoverageError` still converts into `anyhow::Error` via
+/// `?` for callers like `get_coverage_geojson` that don't need the detail.
+#[derive(Debug, thiserror::Error)]
+pub enum CoverageError {
+    #[error("failed to acquire a database connection from the pool: {0}")]
+    PoolExhausted(#[from] diesel::r2d2::PoolError),
+
+    #[error("coverage query task panicked: {0}")]
+    TaskJoin(#[from] tokio::task::JoinError),
+
+    #[error(
+        "bbox query failed for ({west}, {south}) to ({east}, {north}) at resolution {resolution}: {source}"
+    )]
+    BboxQuery {
+        west: f64,
+        south: f64,
+        east: f64,
+        north: f64,
+        resolution: i16,
+        #[source]
+        source: diesel::result::Error,
+    },
+
+    #[error("mvt query failed for tile z={z} x={x} y={y}: {source}")]
+    MvtQuery {
+        z: u8,
+        x: u32,
+        y: u32,
+        #[source]
+        source: diesel::result::Error,
+    },
+}
+
+fn tile_y_to_lat(y: u32, tiles_per_axis: f64) -> f64 {
+    let lat_rad = (std::f64::consts::PI * (1.0 - 2.0 * y as f64 / tiles_per_axis))
+        .sinh()
+        .atan();
+    lat_rad.to_degrees()
+}
+
 #[derive(Clone)]
 pub struct CoverageRepository {
     pool: PgPool,
@@ -66,7 +169,21 @@ impl CoverageRepository {
     /// Get coverage hexes within bounding box for a given resolution and time range
     /// Filters by date range, optional receiver, and optional altitude range
     /// Uses h3_postgis extension for efficient spatial filtering
+    ///
+    /// `bin` selects which rollup table to read from (see `CoverageBin`);
+    /// if `None`, the coarsest bin that still fully covers `start_date..end_date`
+    /// is picked automatically via `bin_for_range`.
+    ///
+    /// Every filter value is passed as a bound parameter (`.bind()`), never
+    /// interpolated into the SQL text — `table`/`date_col` are the only
+    /// pieces still assembled with `format!`, and those come from the
+    /// `CoverageBin` enum rather than request input.
+    ///
+    /// `terrain_class`/`max_terrain_clearance_feet` filter on the terrain
+    /// enrichment populated by `enrich_terrain` (see `crate::terrain`);
+    /// hexes that haven't been enriched yet never match either filter.
     #[allow(clippy::too_many_arguments)]
+    #[instrument(skip(self), fields(resolution, west, east, south, north))]
     pub async fn get_coverage_in_bbox(
         &self,
         resolution: i16,
@@ -80,73 +197,118 @@ impl CoverageRepository {
         min_altitude: Option<i32>,
         max_altitude: Option<i32>,
         limit: i64,
-    ) -> Result<Vec<ReceiverCoverageH3>> {
+        bin: Option<CoverageBin>,
+        terrain_class: Option<crate::terrain::TerrainClass>,
+        max_terrain_clearance_feet: Option<i32>,
+    ) -> Result<Vec<ReceiverCoverageH3>, CoverageError> {
+        let terrain_class = terrain_class.map(crate::terrain::TerrainClass::as_i16);
         let pool = self.pool.clone();
         let limit = limit.min(10000); // Cap at 10k hexes
+        let bin = bin.unwrap_or_else(|| bin_for_range(start_date, end_date));
+        let table = bin.table_name();
+        let date_col = bin.date_column();
+        // Widen the start back to the first day of its bin so the
+        // weekly/monthly row holding `start_date` is read too (Postgres
+        // `date_trunc('week')` weeks start on Monday).
+        let bin_start_date = match bin {
+            CoverageBin::Daily => start_date,
+            CoverageBin::Weekly => {
+                use chrono::Datelike;
+                start_date
+                    - chrono::Duration::days(start_date.weekday().num_days_from_monday().into())
+            }
+            CoverageBin::Monthly => {
+                use chrono::Datelike;
+                start_date.with_day(1).unwrap_or(start_date)
+            }
+        };
+        let query_start = std::time::Instant::now();
 
-        tokio::task::spawn_blocking(move || {
-            let mut conn = pool.get()?;
+        let outcome = tokio::task::spawn_blocking(move || {
+            let mut conn = pool.get().map_err(CoverageError::PoolExhausted)?;
 
             // Use h3_postgis to efficiently filter coverage data within bounding box
             // 1. Create bounding box as PostGIS geography
             // 2. Use h3_polygon_to_cells to get all H3 cells within the bbox
-            // 3. Join with receiver_coverage_h3 to get coverage data for those cells
-
-            // Build SQL - using format! is safe here as all parameters are validated API inputs
-            let base_sql = format!(
+            // 3. Join with receiver_coverage_h3 (or its weekly/monthly rollup) to
+            //    get coverage data for those cells
+            //
+            // `table`/`date_col` come from the `CoverageBin` enum, not user
+            // input, so interpolating them with `format!` is safe; every
+            // filter value is a bound parameter ($1, $2, ...) below. The
+            // optional filters (receiver/altitude) are bound as nullable
+            // columns and gated with a `$n IS NULL OR ...` clause so a
+            // single prepared statement shape covers every combination.
+            let sql = format!(
                 r#"
                 WITH bbox AS (
-                    SELECT ST_MakeEnvelope({}, {}, {}, {}, 4326)::geography AS geog
+                    SELECT ST_MakeEnvelope($1, $2, $3, $4, 4326)::geography AS geog
                 ),
                 cells AS (
-                    SELECT h3_polygon_to_cells(bbox.geog, {}) AS h3_idx
+                    SELECT h3_polygon_to_cells(bbox.geog, $5) AS h3_idx
                     FROM bbox
                 )
-                SELECT rch.h3_index, rch.resolution, rch.receiver_id, rch.date,
+                SELECT rch.h3_index, rch.resolution, rch.receiver_id, rch.{date_col} AS date,
                        rch.fix_count, rch.first_seen_at, rch.last_seen_at,
                        rch.min_altitude_msl_feet, rch.max_altitude_msl_feet,
-                       rch.avg_altitude_msl_feet, rch.updated_at
-                FROM receiver_coverage_h3 rch
+                       rch.avg_altitude_msl_feet, rch.ground_elevation_msl_feet,
+                       rch.terrain_class, rch.updated_at
+                FROM {table} rch
                 INNER JOIN cells c ON rch.h3_index = c.h3_idx::bigint
-                WHERE rch.resolution = {}
-                  AND rch.date >= '{}'
-                  AND rch.date <= '{}'
-                "#,
-                west, south, east, north, resolution, resolution, start_date, end_date
+                WHERE rch.resolution = $5
+                  AND rch.{date_col} >= $9
+                  AND rch.{date_col} <= $10
+                  AND ($6::uuid IS NULL OR rch.receiver_id = $6)
+                  AND ($7::integer IS NULL OR rch.max_altitude_msl_feet >= $7)
+                  AND ($8::integer IS NULL OR rch.min_altitude_msl_feet <= $8)
+                  AND ($12::smallint IS NULL OR rch.terrain_class = $12)
+                  AND (
+                      $13::integer IS NULL
+                      OR (rch.min_altitude_msl_feet - rch.ground_elevation_msl_feet) <= $13
+                  )
+                ORDER BY rch.fix_count DESC LIMIT $11
+                "#
             );
 
-            let mut conditions = Vec::new();
-
-            if let Some(rid) = receiver_id {
-                conditions.push(format!("rch.receiver_id = '{}'", rid));
-            }
-
-            if let Some(min_alt) = min_altitude {
-                conditions.push(format!("rch.max_altitude_msl_feet >= {}", min_alt));
-            }
-
-            if let Some(max_alt) = max_altitude {
-                conditions.push(format!("rch.min_altitude_msl_feet <= {}", max_alt));
-            }
-
-            let mut sql = base_sql;
-            if !conditions.is_empty() {
-                sql.push_str(" AND ");
-                sql.push_str(&conditions.join(" AND "));
-            }
-
-            sql.push_str(&format!(" ORDER BY rch.fix_count DESC LIMIT {}", limit));
-
-            // Execute raw SQL query
-            let query_results: Vec<CoverageQueryResult> = diesel::sql_query(sql).load(&mut conn)?;
+            let query_results: Vec<CoverageQueryResult> = diesel::sql_query(sql)
+                .bind::<sql_types::Double, _>(west)
+                .bind::<sql_types::Double, _>(south)
+                .bind::<sql_types::Double, _>(east)
+                .bind::<sql_types::Double, _>(north)
+                .bind::<sql_types::SmallInt, _>(resolution)
+                .bind::<sql_types::Nullable<sql_types::Uuid>, _>(receiver_id)
+                .bind::<sql_types::Nullable<sql_types::Integer>, _>(min_altitude)
+                .bind::<sql_types::Nullable<sql_types::Integer>, _>(max_altitude)
+                .bind::<sql_types::Date, _>(bin_start_date)
+                .bind::<sql_types::Date, _>(end_date)
+                .bind::<sql_types::BigInt, _>(limit)
+                .bind::<sql_types::Nullable<sql_types::SmallInt>, _>(terrain_class)
+                .bind::<sql_types::Nullable<sql_types::Integer>, _>(max_terrain_clearance_feet)
+                .load(&mut conn)
+                .map_err(|source| CoverageError::BboxQuery {
+                    west,
+                    south,
+                    east,
+                    north,
+                    resolution,
+                    source,
+                })?;
 
             let results: Vec<ReceiverCoverageH3> =
                 query_results.into_iter().map(|r| r.into()).collect();
 
+            histogram!("coverage.repo.bbox_query_ms")
+                .record(query_start.elapsed().as_secs_f64() * 1000.0);
+            counter!("coverage.repo.hexes_returned_total").increment(results.len() as u64);
+            if results.len() as i64 >= limit {
+                counter!("coverage.repo.results_capped_total").increment(1);
+            }
+
             info!(
-                "Found {} coverage hexes (resolution {}) in bbox [{}, {}] to [{}, {}]",
+                "Found {} coverage hexes (resolution {}, bin {:?}) in bbox [{}, {}] to [{}, {}]",
                 results.len(),
                 resolution,
+                bin,
                 south,
                 west,
                 north,
@@ -155,10 +317,14 @@ impl CoverageRepository {
 
             Ok(results)
         })
-        .await?
+        .await;
+
+        outcome.map_err(CoverageError::TaskJoin)?
     }
 
-    /// Get coverage hexes and convert to GeoJSON features
+    /// Get coverage hexes and convert to GeoJSON features. See
+    /// `get_coverage_in_bbox` for the `bin`, `terrain_class`, and
+    /// `max_terrain_clearance_feet` arguments.
     #[allow(clippy::too_many_arguments)]
     pub async fn get_coverage_geojson(
         &self,
@@ -173,6 +339,9 @@ impl CoverageRepository {
         min_altitude: Option<i32>,
         max_altitude: Option<i32>,
         limit: i64,
+        bin: Option<CoverageBin>,
+        terrain_class: Option<crate::terrain::TerrainClass>,
+        max_terrain_clearance_feet: Option<i32>,
     ) -> Result<Vec<CoverageHexFeature>> {
         let coverages = self
             .get_coverage_in_bbox(
@@ -187,6 +356,9 @@ impl CoverageRepository {
                 min_altitude,
                 max_altitude,
                 limit,
+                bin,
+                terrain_class,
+                max_terrain_clearance_feet,
             )
             .await?;
 
@@ -199,6 +371,279 @@ impl CoverageRepository {
         features
     }
 
+    /// Render coverage hexes intersecting an XYZ tile as a binary Mapbox
+    /// Vector Tile. Reuses the same `h3_polygon_to_cells` + join approach as
+    /// `get_coverage_in_bbox`, but the tile itself is assembled in Postgres
+    /// via `ST_AsMVTGeom`/`ST_AsMVT` instead of serializing rows to GeoJSON,
+    /// since clipping and encoding millions of hexes client-side doesn't
+    /// scale.
+    ///
+    /// Every filter value is passed as a bound parameter, the same
+    /// `$n IS NULL OR ...` gating as `get_coverage_in_bbox` for the optional
+    /// receiver/altitude filters.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn get_coverage_mvt(
+        &self,
+        z: u8,
+        x: u32,
+        y: u32,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        receiver_id: Option<Uuid>,
+        min_altitude: Option<i32>,
+        max_altitude: Option<i32>,
+    ) -> Result<Vec<u8>, CoverageError> {
+        let pool = self.pool.clone();
+        let resolution = zoom_to_resolution(z);
+        let (west, south, east, north) = tile_bounds(z, x, y);
+
+        let outcome = tokio::task::spawn_blocking(move || {
+            let mut conn = pool.get().map_err(CoverageError::PoolExhausted)?;
+
+            let sql = r#"
+                WITH bbox AS (
+                    SELECT ST_MakeEnvelope($1, $2, $3, $4, 4326) AS geom
+                ),
+                tile_bbox AS (
+                    SELECT ST_TileEnvelope($5, $6, $7) AS geom
+                ),
+                cells AS (
+                    SELECT h3_polygon_to_cells(bbox.geom::geography, $8) AS h3_idx
+                    FROM bbox
+                ),
+                mvt_source AS (
+                    SELECT
+                        rch.fix_count,
+                        rch.min_altitude_msl_feet,
+                        rch.max_altitude_msl_feet,
+                        rch.avg_altitude_msl_feet,
+                        ST_AsMVTGeom(
+                            ST_Transform(h3_cell_to_boundary_geometry(c.h3_idx), 3857),
+                            tile_bbox.geom
+                        ) AS geom
+                    FROM receiver_coverage_h3 rch
+                    INNER JOIN cells c ON rch.h3_index = c.h3_idx::bigint
+                    CROSS JOIN tile_bbox
+                    WHERE rch.resolution = $8
+                      AND rch.date >= $9
+                      AND rch.date <= $10
+                      AND ($11::uuid IS NULL OR rch.receiver_id = $11)
+                      AND ($12::integer IS NULL OR rch.max_altitude_msl_feet >= $12)
+                      AND ($13::integer IS NULL OR rch.min_altitude_msl_feet <= $13)
+                )
+                SELECT ST_AsMVT(mvt_source, 'coverage', 4096, 'geom') AS mvt
+                FROM mvt_source
+                WHERE geom IS NOT NULL
+                "#;
+
+            let result: MvtQueryResult = diesel::sql_query(sql)
+                .bind::<sql_types::Double, _>(west)
+                .bind::<sql_types::Double, _>(south)
+                .bind::<sql_types::Double, _>(east)
+                .bind::<sql_types::Double, _>(north)
+                .bind::<sql_types::Integer, _>(z as i32)
+                .bind::<sql_types::Integer, _>(x as i32)
+                .bind::<sql_types::Integer, _>(y as i32)
+                .bind::<sql_types::SmallInt, _>(resolution)
+                .bind::<sql_types::Date, _>(start_date)
+                .bind::<sql_types::Date, _>(end_date)
+                .bind::<sql_types::Nullable<sql_types::Uuid>, _>(receiver_id)
+                .bind::<sql_types::Nullable<sql_types::Integer>, _>(min_altitude)
+                .bind::<sql_types::Nullable<sql_types::Integer>, _>(max_altitude)
+                .get_result(&mut conn)
+                .map_err(|source| CoverageError::MvtQuery { z, x, y, source })?;
+
+            let tile = result.mvt.unwrap_or_default();
+
+            info!(
+                "Built coverage MVT for tile z={} x={} y={} (resolution {}), {} bytes",
+                z,
+                x,
+                y,
+                resolution,
+                tile.len()
+            );
+
+            Ok(tile)
+        })
+        .await;
+
+        outcome.map_err(CoverageError::TaskJoin)?
+    }
+
+    /// Rebuild the weekly and monthly rollup tables from daily
+    /// `receiver_coverage_h3` rows covering `start_date..=end_date`, with the
+    /// start widened back to the first day of the bin that holds it. Each
+    /// bin is fully recomputed (not incrementally merged) from the
+    /// authoritative daily data, so re-running for the same range is
+    /// idempotent. Returns `(weekly_rows, monthly_rows)` upserted.
+    pub async fn rebuild_rollups(
+        &self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<(usize, usize)> {
+        let weekly = self
+            .rebuild_rollup_bin(CoverageBin::Weekly, "week", start_date, end_date)
+            .await?;
+        let monthly = self
+            .rebuild_rollup_bin(CoverageBin::Monthly, "month", start_date, end_date)
+            .await?;
+
+        Ok((weekly, monthly))
+    }
+
+    /// Aggregate daily rows into one rollup table, truncating `date` to
+    /// `trunc_unit` ("week" or "month") via Postgres `date_trunc`. Sums
+    /// `fix_count`, takes MIN/MAX of the altitude bounds, and recomputes
+    /// `avg_altitude_msl_feet` as a fix-count-weighted average so a busy
+    /// day isn't diluted by a quiet one on equal footing. Also carries
+    /// forward `ground_elevation_msl_feet`/`terrain_class` (constant per
+    /// hex, so MAX just picks the non-null value) so `terrain_class`/
+    /// `max_terrain_clearance_feet` filters on `get_coverage_in_bbox` work
+    /// against weekly/monthly bins, not just the daily table.
+    async fn rebuild_rollup_bin(
+        &self,
+        bin: CoverageBin,
+        trunc_unit: &'static str,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<usize> {
+        let pool = self.pool.clone();
+        let table = bin.table_name();
+
+        tokio::task::spawn_blocking(move || {
+            let mut conn = pool.get()?;
+
+            #[derive(QueryableByName, Debug)]
+            struct RollupRow {
+                #[diesel(sql_type = sql_types::BigInt)]
+                h3_index: i64,
+                #[diesel(sql_type = sql_types::SmallInt)]
+                resolution: i16,
+                #[diesel(sql_type = sql_types::Uuid)]
+                receiver_id: Uuid,
+                #[diesel(sql_type = sql_types::Date)]
+                bin_start: NaiveDate,
+                #[diesel(sql_type = sql_types::BigInt)]
+                fix_count: i64,
+                #[diesel(sql_type = sql_types::Timestamptz)]
+                first_seen_at: DateTime<Utc>,
+                #[diesel(sql_type = sql_types::Timestamptz)]
+                last_seen_at: DateTime<Utc>,
+                #[diesel(sql_type = sql_types::Nullable<sql_types::Integer>)]
+                min_altitude_msl_feet: Option<i32>,
+                #[diesel(sql_type = sql_types::Nullable<sql_types::Integer>)]
+                max_altitude_msl_feet: Option<i32>,
+                #[diesel(sql_type = sql_types::Nullable<sql_types::Integer>)]
+                avg_altitude_msl_feet: Option<i32>,
+                #[diesel(sql_type = sql_types::Nullable<sql_types::Integer>)]
+                ground_elevation_msl_feet: Option<i32>,
+                #[diesel(sql_type = sql_types::Nullable<sql_types::SmallInt>)]
+                terrain_class: Option<i16>,
+            }
+
+            let rows: Vec<RollupRow> = diesel::sql_query(format!(
+                r#"
+                SELECT
+                    h3_index,
+                    resolution,
+                    receiver_id,
+                    date_trunc('{trunc_unit}', date)::date AS bin_start,
+                    SUM(fix_count)::bigint AS fix_count,
+                    MIN(first_seen_at) AS first_seen_at,
+                    MAX(last_seen_at) AS last_seen_at,
+                    MIN(min_altitude_msl_feet) AS min_altitude_msl_feet,
+                    MAX(max_altitude_msl_feet) AS max_altitude_msl_feet,
+                    (SUM(COALESCE(avg_altitude_msl_feet, 0) * fix_count) / NULLIF(SUM(fix_count), 0))::int
+                        AS avg_altitude_msl_feet,
+                    -- ground_elevation_msl_feet/terrain_class are per-hex
+                    -- terrain facts (same value on every daily row for a
+                    -- given h3_index), so any non-null value in the range
+                    -- is correct; MAX just picks one.
+                    MAX(ground_elevation_msl_feet) AS ground_elevation_msl_feet,
+                    MAX(terrain_class) AS terrain_class
+                FROM receiver_coverage_h3
+                WHERE date >= date_trunc('{trunc_unit}', $1::date)::date AND date <= $2
+                GROUP BY h3_index, resolution, receiver_id, bin_start
+                "#
+            ))
+            .bind::<sql_types::Date, _>(start_date)
+            .bind::<sql_types::Date, _>(end_date)
+            .load(&mut conn)
+            .context("Failed to aggregate coverage rollup rows")?;
+
+            if rows.is_empty() {
+                return Ok(0);
+            }
+
+            let count = rows.len();
+
+            let h3_indexes: Vec<i64> = rows.iter().map(|r| r.h3_index).collect();
+            let resolutions: Vec<i16> = rows.iter().map(|r| r.resolution).collect();
+            let receiver_ids: Vec<Uuid> = rows.iter().map(|r| r.receiver_id).collect();
+            let bin_starts: Vec<NaiveDate> = rows.iter().map(|r| r.bin_start).collect();
+            let fix_counts: Vec<i32> = rows.iter().map(|r| r.fix_count as i32).collect();
+            let first_seen_ats: Vec<_> = rows.iter().map(|r| r.first_seen_at).collect();
+            let last_seen_ats: Vec<_> = rows.iter().map(|r| r.last_seen_at).collect();
+            let min_altitudes: Vec<Option<i32>> =
+                rows.iter().map(|r| r.min_altitude_msl_feet).collect();
+            let max_altitudes: Vec<Option<i32>> =
+                rows.iter().map(|r| r.max_altitude_msl_feet).collect();
+            let avg_altitudes: Vec<Option<i32>> =
+                rows.iter().map(|r| r.avg_altitude_msl_feet).collect();
+            let ground_elevations: Vec<Option<i32>> =
+                rows.iter().map(|r| r.ground_elevation_msl_feet).collect();
+            let terrain_classes: Vec<Option<i16>> =
+                rows.iter().map(|r| r.terrain_class).collect();
+
+            diesel::sql_query(format!(
+                r#"
+                INSERT INTO {table} (
+                    h3_index, resolution, receiver_id, bin_start,
+                    fix_count, first_seen_at, last_seen_at,
+                    min_altitude_msl_feet, max_altitude_msl_feet, avg_altitude_msl_feet,
+                    ground_elevation_msl_feet, terrain_class
+                )
+                SELECT * FROM UNNEST(
+                    $1::bigint[], $2::smallint[], $3::uuid[], $4::date[],
+                    $5::integer[], $6::timestamptz[], $7::timestamptz[],
+                    $8::integer[], $9::integer[], $10::integer[],
+                    $11::integer[], $12::smallint[]
+                )
+                ON CONFLICT (h3_index, resolution, receiver_id, bin_start) DO UPDATE SET
+                    fix_count = EXCLUDED.fix_count,
+                    first_seen_at = LEAST({table}.first_seen_at, EXCLUDED.first_seen_at),
+                    last_seen_at = GREATEST({table}.last_seen_at, EXCLUDED.last_seen_at),
+                    min_altitude_msl_feet = LEAST({table}.min_altitude_msl_feet, EXCLUDED.min_altitude_msl_feet),
+                    max_altitude_msl_feet = GREATEST({table}.max_altitude_msl_feet, EXCLUDED.max_altitude_msl_feet),
+                    avg_altitude_msl_feet = EXCLUDED.avg_altitude_msl_feet,
+                    ground_elevation_msl_feet = COALESCE(EXCLUDED.ground_elevation_msl_feet, {table}.ground_elevation_msl_feet),
+                    terrain_class = COALESCE(EXCLUDED.terrain_class, {table}.terrain_class),
+                    updated_at = NOW()
+                "#
+            ))
+            .bind::<sql_types::Array<sql_types::BigInt>, _>(h3_indexes)
+            .bind::<sql_types::Array<sql_types::SmallInt>, _>(resolutions)
+            .bind::<sql_types::Array<sql_types::Uuid>, _>(receiver_ids)
+            .bind::<sql_types::Array<sql_types::Date>, _>(bin_starts)
+            .bind::<sql_types::Array<sql_types::Integer>, _>(fix_counts)
+            .bind::<sql_types::Array<sql_types::Timestamptz>, _>(first_seen_ats)
+            .bind::<sql_types::Array<sql_types::Timestamptz>, _>(last_seen_ats)
+            .bind::<sql_types::Array<sql_types::Nullable<sql_types::Integer>>, _>(min_altitudes)
+            .bind::<sql_types::Array<sql_types::Nullable<sql_types::Integer>>, _>(max_altitudes)
+            .bind::<sql_types::Array<sql_types::Nullable<sql_types::Integer>>, _>(avg_altitudes)
+            .bind::<sql_types::Array<sql_types::Nullable<sql_types::Integer>>, _>(ground_elevations)
+            .bind::<sql_types::Array<sql_types::Nullable<sql_types::SmallInt>>, _>(terrain_classes)
+            .execute(&mut conn)
+            .context("Failed to upsert coverage rollup rows")?;
+
+            info!("Rebuilt {} rows in {} for {} to {}", count, table, start_date, end_date);
+
+            Ok(count)
+        })
+        .await?
+    }
+
     /// Upsert coverage data in batches (used by aggregation command)
     pub async fn upsert_coverage_batch(
         &self,
@@ -206,12 +651,14 @@ impl CoverageRepository {
     ) -> Result<usize> {
         let pool = self.pool.clone();
         let total_count = coverages.len();
+        let batch_start = std::time::Instant::now();
 
         tokio::task::spawn_blocking(move || {
             let mut conn = pool.get()?;
 
             // Process in chunks of 5000 to avoid parameter limits and improve performance
             const CHUNK_SIZE: usize = 5000;
+            let mut chunks_written = 0u64;
 
             for chunk in coverages.chunks(CHUNK_SIZE) {
                 // Build arrays for bulk insert using UNNEST
@@ -273,8 +720,15 @@ impl CoverageRepository {
                     avg_altitudes,
                 )
                 .execute(&mut conn)?;
+
+                chunks_written += 1;
             }
 
+            counter!("coverage.repo.upsert_records_total").increment(total_count as u64);
+            counter!("coverage.repo.upsert_chunks_total").increment(chunks_written);
+            histogram!("coverage.repo.upsert_batch_ms")
+                .record(batch_start.elapsed().as_secs_f64() * 1000.0);
+
             info!(
                 "Upserted {} coverage records ({} chunks of max {})",
                 total_count,
@@ -286,4 +740,128 @@ impl CoverageRepository {
         })
         .await?
     }
+
+    /// Backfill `ground_elevation_msl_feet`/`terrain_class` for up to
+    /// `BATCH_SIZE` coverage hexes that haven't been enriched yet. Looks up
+    /// each hex's centroid (`crate::coverage::h3_centroid`) against
+    /// `elevation` (EGM2008-corrected ground elevation, same as the AGL
+    /// backfill in `agl_backfill.rs`) and `terrain` (the downloaded
+    /// land-cover reference data, see `crate::terrain`), then bulk-UPDATEs
+    /// the matched rows. Call repeatedly (e.g. from a background task) until
+    /// it returns 0 to drain the backlog; already-enriched rows are never
+    /// re-selected, so repeated calls are idempotent.
+    pub async fn enrich_terrain(
+        &self,
+        elevation: &ElevationService,
+        terrain: &TerrainLookup,
+    ) -> Result<usize> {
+        const BATCH_SIZE: i64 = 500;
+
+        #[derive(QueryableByName, Debug)]
+        struct PendingRow {
+            #[diesel(sql_type = sql_types::BigInt)]
+            h3_index: i64,
+        }
+
+        let pending: Vec<PendingRow> = {
+            let pool = self.pool.clone();
+            tokio::task::spawn_blocking(move || {
+                let mut conn = pool.get()?;
+                diesel::sql_query(
+                    "SELECT DISTINCT h3_index FROM receiver_coverage_h3 \
+                     WHERE ground_elevation_msl_feet IS NULL LIMIT $1",
+                )
+                .bind::<sql_types::BigInt, _>(BATCH_SIZE)
+                .load(&mut conn)
+                .context("Failed to load coverage hexes pending terrain enrichment")
+            })
+            .await??
+        };
+
+        if pending.is_empty() {
+            return Ok(0);
+        }
+
+        let mut h3_indexes = Vec::with_capacity(pending.len());
+        let mut ground_elevations: Vec<Option<i32>> = Vec::with_capacity(pending.len());
+        let mut terrain_classes: Vec<Option<i16>> = Vec::with_capacity(pending.len());
+
+        for row in &pending {
+            let (lat, lon) = crate::coverage::h3_centroid(row.h3_index)?;
+
+            let ground_elevation_msl_feet = elevation
+                .elevation_egm2008(lat, lon)
+                .await
+                .ok()
+                .flatten()
+                .map(|elevation_m| {
+                    // Convert elevation from meters to feet (1 meter = 3.28084 feet)
+                    let elevation_ft = elevation_m * 3.28084;
+                    elevation_ft.round() as i32
+                });
+
+            let terrain_class = terrain
+                .get(row.h3_index)
+                .await
+                .map(crate::terrain::TerrainClass::as_i16);
+
+            h3_indexes.push(row.h3_index);
+            ground_elevations.push(ground_elevation_msl_feet);
+            terrain_classes.push(terrain_class);
+        }
+
+        let count = h3_indexes.len();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let mut conn = pool.get()?;
+
+            diesel::sql_query(
+                r#"
+                UPDATE receiver_coverage_h3 AS rch SET
+                    ground_elevation_msl_feet = u.ground_elevation_msl_feet,
+                    terrain_class = u.terrain_class,
+                    updated_at = NOW()
+                FROM UNNEST($1::bigint[], $2::integer[], $3::smallint[])
+                    AS u(h3_index, ground_elevation_msl_feet, terrain_class)
+                WHERE rch.h3_index = u.h3_index
+                "#,
+            )
+            .bind::<sql_types::Array<sql_types::BigInt>, _>(h3_indexes)
+            .bind::<sql_types::Array<sql_types::Nullable<sql_types::Integer>>, _>(ground_elevations)
+            .bind::<sql_types::Array<sql_types::Nullable<sql_types::SmallInt>>, _>(terrain_classes)
+            .execute(&mut conn)
+            .context("Failed to persist terrain enrichment for coverage hexes")?;
+
+            info!("Enriched terrain data for {} coverage hexes", count);
+
+            Ok(count)
+        })
+        .await?
+    }
+}
+
+/// Background task that repeatedly drains `CoverageRepository::enrich_terrain`'s
+/// backlog of un-enriched coverage hexes. Runs batches back to back while
+/// each one tags any hexes (so a backlog built up while the server was down
+/// drains quickly), then falls back to polling every few minutes once caught
+/// up, matching the resilience of the repo's other background loops (e.g.
+/// `crate::metrics::analytics_metrics_task`).
+pub async fn coverage_terrain_enrichment_task(
+    pool: PgPool,
+    elevation: crate::elevation::ElevationService,
+    terrain: crate::terrain::TerrainLookup,
+) {
+    let repo = CoverageRepository::new(pool);
+
+    loop {
+        match repo.enrich_terrain(&elevation, &terrain).await {
+            Ok(0) => tokio::time::sleep(std::time::Duration::from_secs(300)).await,
+            Ok(count) => info!("Terrain enrichment: tagged {} coverage hexes", count),
+            Err(e) => {
+                tracing::warn!("Terrain enrichment batch failed: {:#}", e);
+                tokio::time::sleep(std::time::Duration::from_secs(300)).await;
+            }
+        }
+    }
 }
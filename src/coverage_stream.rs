@@ -0,0 +1,113 @@
+//! Real-time coverage update fan-out via PostgreSQL LISTEN/NOTIFY.
+//!
+//! Coverage aggregation (`receiver_coverage_h3` upserts, see
+//! the binary's `commands/aggregate_coverage.rs`) runs as a periodic batch job, so map
+//! clients can't simply poll `get_coverage_geojson` often enough to feel
+//! live. A trigger on `receiver_coverage_h3` (see the
+//! `add_coverage_updates_trigger` migration) fires `pg_notify` on every
+//! insert/update, and the listener here re-broadcasts those notifications to
+//! every subscribed SSE connection over a `tokio::sync::broadcast` channel,
+//! so one long-lived listen connection feeds many clients.
+
+use anyhow::Result;
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tracing::{error, info, warn};
+
+use crate::coverage::CoverageNotification;
+
+/// Postgres NOTIFY channel fired by the `receiver_coverage_h3` trigger.
+const COVERAGE_UPDATES_CHANNEL: &str = "coverage_updates";
+
+/// Number of buffered updates per subscriber before a slow consumer starts
+/// missing messages (`broadcast::error::RecvError::Lagged`).
+const BROADCAST_CAPACITY: usize = 1024;
+
+/// Fan-out service for real-time coverage updates. Cheap to clone; every
+/// clone shares the same underlying broadcast channel.
+#[derive(Clone)]
+pub struct CoverageStreamService {
+    sender: broadcast::Sender<CoverageNotification>,
+}
+
+impl CoverageStreamService {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(BROADCAST_CAPACITY);
+        Self { sender }
+    }
+
+    /// Subscribe to the stream of coverage updates. Each SSE connection gets
+    /// its own receiver backed by the same broadcast channel.
+    pub fn subscribe(&self) -> broadcast::Receiver<CoverageNotification> {
+        self.sender.subscribe()
+    }
+
+    /// Spawn a background task that `LISTEN`s on `coverage_updates` and
+    /// re-broadcasts every notification to subscribers. Reconnects with a
+    /// fixed delay if the listen connection drops.
+    pub fn spawn_listener(&self) -> tokio::task::JoinHandle<()> {
+        let sender = self.sender.clone();
+
+        tokio::spawn(async move {
+            loop {
+                if let Err(e) = listen_for_coverage_updates(&sender).await {
+                    error!(
+                        error = %e,
+                        "coverage update listener disconnected, retrying in 5s"
+                    );
+                }
+                tokio::time::sleep(Duration::from_secs(5)).await;
+            }
+        })
+    }
+}
+
+impl Default for CoverageStreamService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Open a dedicated `tokio_postgres` connection (the r2d2 pool can't hold a
+/// connection open to block on `NOTIFY`), `LISTEN` on `coverage_updates`, and
+/// re-broadcast each payload until the connection is lost.
+async fn listen_for_coverage_updates(
+    sender: &broadcast::Sender<CoverageNotification>,
+) -> Result<()> {
+    use futures_util::{stream, StreamExt};
+    use tokio_postgres::{AsyncMessage, NoTls};
+
+    let database_url = std::env::var("DATABASE_URL")
+        .map_err(|_| anyhow::anyhow!("DATABASE_URL environment variable not set"))?;
+
+    let (client, mut connection) = tokio_postgres::connect(&database_url, NoTls).await?;
+
+    client
+        .batch_execute(&format!("LISTEN {COVERAGE_UPDATES_CHANNEL}"))
+        .await?;
+
+    info!("Listening for coverage updates on channel {COVERAGE_UPDATES_CHANNEL}");
+
+    let mut messages = stream::poll_fn(move |cx| connection.poll_message(cx));
+
+    while let Some(message) = messages.next().await {
+        match message {
+            Ok(AsyncMessage::Notification(notification)) => {
+                match serde_json::from_str::<CoverageNotification>(notification.payload()) {
+                    Ok(update) => {
+                        // Err just means no subscribers are currently listening, which
+                        // is fine when nobody has the coverage map open.
+                        let _ = sender.send(update);
+                    }
+                    Err(e) => warn!("Failed to parse coverage_updates payload: {}", e),
+                }
+            }
+            Ok(_) => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    Err(anyhow::anyhow!(
+        "coverage_updates listen connection closed unexpectedly"
+    ))
+}
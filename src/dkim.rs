@@ -0,0 +1,323 @@
+//! Optional DKIM signing for outgoing mail
+//!
+//! Configured entirely from the environment so that local/Mailpit flows are
+//! unaffected when no key is present:
+//! - `DKIM_PRIVATE_KEY`: path to a PEM-encoded private key
+//! - `DKIM_DOMAIN`: the `d=` domain to sign for
+//! - `DKIM_SELECTOR`: the `s=` selector
+//! - `DKIM_ALGORITHM`: `rsa-sha256` (default) or `ed25519-sha256`
+//!
+//! Only relaxed/relaxed canonicalization (RFC 6376 section 3.4.2) is
+//! supported, which is what every major receiving MTA expects in practice.
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use sha2::{Digest, Sha256};
+use std::sync::OnceLock;
+
+/// The headers that get included in the signature, in the order they should
+/// appear in `h=`. These are the headers every email we send actually sets.
+const SIGNED_HEADERS: &[&str] = &["From", "To", "Subject", "Date", "MIME-Version", "Content-Type"];
+
+enum SigningKey {
+    Rsa(Box<rsa::RsaPrivateKey>),
+    Ed25519(Box<ed25519_dalek::SigningKey>),
+}
+
+pub struct DkimSigner {
+    domain: String,
+    selector: String,
+    key: SigningKey,
+}
+
+impl DkimSigner {
+    /// Build a signer from `DKIM_PRIVATE_KEY` / `DKIM_DOMAIN` / `DKIM_SELECTOR` /
+    /// `DKIM_ALGORITHM`. Returns `None` (with a one-time warning) if no key is
+    /// configured, so callers can treat signing as a no-op.
+    pub fn from_env() -> Option<Self> {
+        static WARNED: OnceLock<()> = OnceLock::new();
+
+        let key_path = match std::env::var("DKIM_PRIVATE_KEY") {
+            Ok(path) => path,
+            Err(_) => {
+                WARNED.get_or_init(|| {
+                    tracing::warn!(
+                        "DKIM_PRIVATE_KEY not set; outgoing mail will not be DKIM-signed"
+                    );
+                });
+                return None;
+            }
+        };
+
+        let domain = match std::env::var("DKIM_DOMAIN") {
+            Ok(d) => d,
+            Err(_) => {
+                tracing::warn!("DKIM_PRIVATE_KEY is set but DKIM_DOMAIN is missing; skipping DKIM signing");
+                return None;
+            }
+        };
+        let selector = match std::env::var("DKIM_SELECTOR") {
+            Ok(s) => s,
+            Err(_) => {
+                tracing::warn!(
+                    "DKIM_PRIVATE_KEY is set but DKIM_SELECTOR is missing; skipping DKIM signing"
+                );
+                return None;
+            }
+        };
+        let algorithm =
+            std::env::var("DKIM_ALGORITHM").unwrap_or_else(|_| "rsa-sha256".to_string());
+
+        let pem = match std::fs::read_to_string(&key_path) {
+            Ok(pem) => pem,
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to read DKIM_PRIVATE_KEY at {}: {}; skipping DKIM signing",
+                    key_path,
+                    e
+                );
+                return None;
+            }
+        };
+
+        let key = match algorithm.as_str() {
+            "rsa-sha256" => match rsa::RsaPrivateKey::from_pkcs8_pem(&pem)
+                .or_else(|_| rsa::RsaPrivateKey::from_pkcs1_pem(&pem))
+            {
+                Ok(k) => SigningKey::Rsa(Box::new(k)),
+                Err(e) => {
+                    tracing::warn!("Failed to parse DKIM RSA private key: {}; skipping DKIM signing", e);
+                    return None;
+                }
+            },
+            "ed25519-sha256" => match ed25519_dalek::SigningKey::from_pkcs8_pem(&pem) {
+                Ok(k) => SigningKey::Ed25519(Box::new(k)),
+                Err(e) => {
+                    tracing::warn!(
+                        "Failed to parse DKIM Ed25519 private key: {}; skipping DKIM signing",
+                        e
+                    );
+                    return None;
+                }
+            },
+            other => {
+                tracing::warn!("Unknown DKIM_ALGORITHM '{}'; skipping DKIM signing", other);
+                return None;
+            }
+        };
+
+        tracing::info!(
+            "DKIM signing enabled for domain={} selector={} algorithm={}",
+            domain,
+            selector,
+            algorithm
+        );
+
+        Some(Self {
+            domain,
+            selector,
+            key,
+        })
+    }
+
+    fn algorithm_tag(&self) -> &'static str {
+        match self.key {
+            SigningKey::Rsa(_) => "rsa-sha256",
+            SigningKey::Ed25519(_) => "ed25519-sha256",
+        }
+    }
+
+    /// Relaxed body canonicalization (RFC 6376 section 3.4.4): split on CRLF,
+    /// collapse runs of WSP to a single space, trim trailing WSP per line,
+    /// and reduce trailing empty lines to a single CRLF.
+    fn canonicalize_body(body: &[u8]) -> Vec<u8> {
+        let text = String::from_utf8_lossy(body);
+        let mut lines: Vec<String> = text
+            .split("\r\n")
+            .map(|line| {
+                let collapsed = line
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                if line.starts_with(|c: char| c.is_whitespace()) && !collapsed.is_empty() {
+                    format!(" {}", collapsed)
+                } else {
+                    collapsed
+                }
+            })
+            .collect();
+
+        while lines.last().is_some_and(|l| l.is_empty()) {
+            lines.pop();
+        }
+        let mut canonical = lines.join("\r\n");
+        canonical.push_str("\r\n");
+        canonical.into_bytes()
+    }
+
+    /// Relaxed header canonicalization for a single header (RFC 6376 section
+    /// 3.4.2): lowercase the name, unfold, collapse WSP, trim.
+    fn canonicalize_header(name: &str, value: &str) -> String {
+        let collapsed = value.split_whitespace().collect::<Vec<_>>().join(" ");
+        format!("{}:{}", name.to_ascii_lowercase(), collapsed.trim())
+    }
+
+    fn sign_bytes(&self, data: &[u8]) -> Vec<u8> {
+        match &self.key {
+            SigningKey::Rsa(key) => {
+                use rsa::pkcs1v15::SigningKey as RsaSigningKey;
+                use rsa::signature::{SignatureEncoding, Signer};
+
+                let mut hasher = Sha256::new();
+                hasher.update(data);
+                let digest = hasher.finalize();
+                let signing_key = RsaSigningKey::<Sha256>::new(key.as_ref().clone());
+                let signature = signing_key.sign_prehash(&digest).unwrap_or_else(|_| {
+                    // sign_prehash only fails on malformed keys, which from_pkcs8_pem
+                    // would already have rejected.
+                    unreachable!("DKIM RSA signing failed on a previously-validated key")
+                });
+                signature.to_vec()
+            }
+            SigningKey::Ed25519(key) => {
+                use ed25519_dalek::Signer;
+                key.sign(data).to_bytes().to_vec()
+            }
+        }
+    }
+
+    /// Compute the `DKIM-Signature:` header value (without the trailing
+    /// CRLF) for the given header/value pairs (only those present in
+    /// `SIGNED_HEADERS` are considered) and raw message body.
+    pub fn sign(&self, headers: &[(String, String)], body: &[u8]) -> String {
+        let body_hash = {
+            let mut hasher = Sha256::new();
+            hasher.update(Self::canonicalize_body(body));
+            BASE64.encode(hasher.finalize())
+        };
+
+        let signed_header_names: Vec<&str> = SIGNED_HEADERS
+            .iter()
+            .copied()
+            .filter(|name| headers.iter().any(|(k, _)| k.eq_ignore_ascii_case(name)))
+            .collect();
+
+        let unsigned_dkim_header = format!(
+            "v=1; a={}; c=relaxed/relaxed; d={}; s={}; h={}; bh={}; b=",
+            self.algorithm_tag(),
+            self.domain,
+            self.selector,
+            signed_header_names.join(":"),
+            body_hash,
+        );
+
+        let mut signing_input = String::new();
+        for name in &signed_header_names {
+            if let Some((_, value)) = headers.iter().find(|(k, _)| k.eq_ignore_ascii_case(name)) {
+                signing_input.push_str(&Self::canonicalize_header(name, value));
+                signing_input.push_str("\r\n");
+            }
+        }
+        signing_input.push_str(&Self::canonicalize_header("DKIM-Signature", &unsigned_dkim_header));
+
+        let signature = BASE64.encode(self.sign_bytes(signing_input.as_bytes()));
+
+        format!("{}{}", unsigned_dkim_header, signature)
+    }
+
+    /// Sign a raw RFC 5322 message (as produced by `Message::formatted`) and
+    /// return the message with a `DKIM-Signature:` header prepended.
+    pub fn sign_message(&self, raw: &[u8]) -> Vec<u8> {
+        let split_at = raw
+            .windows(4)
+            .position(|w| w == b"\r\n\r\n")
+            .map(|i| i + 4)
+            .unwrap_or(raw.len());
+        let (header_block, body) = raw.split_at(split_at);
+
+        let headers = String::from_utf8_lossy(header_block)
+            .lines()
+            .fold(Vec::<(String, String)>::new(), |mut acc, line| {
+                if line.starts_with([' ', '\t']) {
+                    if let Some((_, last_value)) = acc.last_mut() {
+                        last_value.push(' ');
+                        last_value.push_str(line.trim());
+                    }
+                } else if let Some((name, value)) = line.split_once(':') {
+                    acc.push((name.trim().to_string(), value.trim().to_string()));
+                }
+                acc
+            });
+
+        let dkim_header = self.sign(&headers, body);
+
+        let mut signed = format!("DKIM-Signature: {}\r\n", dkim_header).into_bytes();
+        signed.extend_from_slice(raw);
+        signed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // RFC 6376 section 3.4.4: runs of WSP within a line collapse to a single
+    // SP (leading included), trailing WSP is dropped entirely (not
+    // collapsed), and the CRLF line ending is preserved.
+    #[test]
+    fn canonicalize_body_collapses_internal_whitespace() {
+        let body = b"C\t D  E\r\n";
+        assert_eq!(DkimSigner::canonicalize_body(body), b"C D E\r\n");
+    }
+
+    #[test]
+    fn canonicalize_body_collapses_leading_whitespace_to_single_space() {
+        let body = b"  C  D\r\n";
+        assert_eq!(DkimSigner::canonicalize_body(body), b" C D\r\n");
+    }
+
+    #[test]
+    fn canonicalize_body_drops_trailing_whitespace() {
+        let body = b"C D   \r\n";
+        assert_eq!(DkimSigner::canonicalize_body(body), b"C D\r\n");
+    }
+
+    #[test]
+    fn canonicalize_body_reduces_whitespace_only_line_to_empty() {
+        let body = b"C\r\n   \r\nD\r\n";
+        assert_eq!(DkimSigner::canonicalize_body(body), b"C\r\n\r\nD\r\n");
+    }
+
+    // RFC 6376 section 3.4.3: trailing empty lines are removed, leaving
+    // exactly one CRLF at the end of a non-empty body.
+    #[test]
+    fn canonicalize_body_reduces_trailing_empty_lines_to_one_crlf() {
+        let body = b"C D\r\n\r\n\r\n";
+        assert_eq!(DkimSigner::canonicalize_body(body), b"C D\r\n");
+    }
+
+    // RFC 6376 section 3.4.3: a wholly empty body canonicalizes to a single
+    // CRLF, never the empty string.
+    #[test]
+    fn canonicalize_body_empty_input_is_single_crlf() {
+        assert_eq!(DkimSigner::canonicalize_body(b""), b"\r\n");
+    }
+
+    // RFC 6376 section 3.4.2: header name lowercased, value unfolded and
+    // trimmed, internal WSP runs collapsed to a single SP.
+    #[test]
+    fn canonicalize_header_lowercases_name_and_collapses_value() {
+        assert_eq!(
+            DkimSigner::canonicalize_header("Subject", "  Hello   World  "),
+            "subject:Hello World"
+        );
+    }
+
+    #[test]
+    fn canonicalize_header_unfolds_continuation_whitespace() {
+        assert_eq!(
+            DkimSigner::canonicalize_header("To", "a@example.com,\r\n b@example.com"),
+            "to:a@example.com, b@example.com"
+        );
+    }
+}
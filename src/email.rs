@@ -7,6 +7,12 @@ use lettre::{
     },
 };
 
+use crate::dkim::DkimSigner;
+use crate::email_templates::{
+    EmailVerificationTemplate, FlightCompletionTemplate, MailTemplate, PasswordResetTemplate,
+    PilotInvitationTemplate,
+};
+
 /// Get the staging prefix for email subjects
 /// Returns "[STAGING] " if SOAR_ENV=staging, empty string otherwise
 fn get_staging_prefix() -> &'static str {
@@ -16,10 +22,23 @@ fn get_staging_prefix() -> &'static str {
     }
 }
 
+/// Build a `text/plain` + `text/html` alternative part for a template, so
+/// clients render the HTML layout but text-only clients still get the plain
+/// body that shipped before templating existed.
+fn alternative_body(template: &dyn MailTemplate) -> lettre::message::MultiPart {
+    use lettre::message::SinglePart;
+
+    lettre::message::MultiPart::alternative()
+        .singlepart(SinglePart::plain(template.text_body()))
+        .singlepart(SinglePart::html(template.html_body()))
+}
+
 pub struct EmailService {
     mailer: AsyncSmtpTransport<Tokio1Executor>,
     from_email: String,
     from_name: String,
+    /// `None` when no DKIM key is configured, in which case signing is a no-op.
+    dkim: Option<DkimSigner>,
 }
 
 impl EmailService {
@@ -89,9 +108,23 @@ impl EmailService {
             mailer,
             from_email,
             from_name,
+            dkim: DkimSigner::from_env(),
         })
     }
 
+    /// Send a message, DKIM-signing it first if a signing key is configured.
+    async fn send_message(&self, email: Message) -> Result<Response> {
+        match &self.dkim {
+            Some(signer) => {
+                let envelope = email.envelope().clone();
+                let signed = signer.sign_message(&email.formatted());
+                let response = self.mailer.send_raw(&envelope, &signed).await?;
+                Ok(response)
+            }
+            None => Ok(self.mailer.send(email).await?),
+        }
+    }
+
     pub async fn send_password_reset_email(
         &self,
         to_email: &str,
@@ -104,31 +137,18 @@ impl EmailService {
         let reset_url = format!("{}/reset-password?token={}", base_url, reset_token);
 
         let subject = format!("{}Password Reset Request - SOAR", get_staging_prefix());
-        let body = format!(
-            r#"Hello {},
-
-We received a request to reset your password for your SOAR account.
-
-To reset your password, please click the following link:
-{}
-
-This link will expire in 1 hour for security reasons.
-
-If you did not request a password reset, please ignore this email and your password will remain unchanged.
-
-Best regards,
-The SOAR Team"#,
-            to_name, reset_url
-        );
+        let template = PasswordResetTemplate {
+            to_name: to_name.to_string(),
+            reset_url,
+        };
 
         let email = Message::builder()
             .from(format!("{} <{}>", self.from_name, self.from_email).parse()?)
             .to(format!("{} <{}>", to_name, to_email).parse()?)
             .subject(subject)
-            .header(ContentType::TEXT_PLAIN)
-            .body(body)?;
+            .multipart(alternative_body(&template))?;
 
-        let response = self.mailer.send(email).await?;
+        let response = self.send_message(email).await?;
         Ok(response)
     }
 
@@ -144,31 +164,18 @@ The SOAR Team"#,
         let verification_url = format!("{}/verify-email?token={}", base_url, verification_token);
 
         let subject = format!("{}Verify Your Email Address - SOAR", get_staging_prefix());
-        let body = format!(
-            r#"Hello {},
-
-Thank you for registering with SOAR! To complete your account setup, please verify your email address.
-
-Click the following link to verify your email:
-{}
-
-This link will expire in 24 hours for security reasons.
-
-If you did not create an account with SOAR, please ignore this email.
-
-Best regards,
-The SOAR Team"#,
-            to_name, verification_url
-        );
+        let template = EmailVerificationTemplate {
+            to_name: to_name.to_string(),
+            verification_url,
+        };
 
         let email = Message::builder()
             .from(format!("{} <{}>", self.from_name, self.from_email).parse()?)
             .to(format!("{} <{}>", to_name, to_email).parse()?)
             .subject(subject)
-            .header(ContentType::TEXT_PLAIN)
-            .body(body)?;
+            .multipart(alternative_body(&template))?;
 
-        let response = self.mailer.send(email).await?;
+        let response = self.send_message(email).await?;
         Ok(response)
     }
 
@@ -192,37 +199,18 @@ The SOAR Team"#,
             "{}You've been invited to join SOAR - Complete Your Registration",
             get_staging_prefix()
         );
-        let body = format!(
-            r#"Hello {},
-
-You've been added to your club's roster on SOAR! To access your account and manage your flight information, please complete your registration by setting a password.
-
-Click the following link to complete your registration:
-{}
-
-This link will expire in 72 hours for security reasons.
-
-Once you've set your password, you'll be able to:
-- View your flight history
-- Track your progress and achievements
-- Receive flight notifications
-- Access club information
-
-If you believe you received this email in error, please ignore it or contact your club administrator.
-
-Best regards,
-The SOAR Team"#,
-            to_name, registration_url
-        );
+        let template = PilotInvitationTemplate {
+            to_name: to_name.to_string(),
+            registration_url,
+        };
 
         let email = Message::builder()
             .from(format!("{} <{}>", self.from_name, self.from_email).parse()?)
             .to(format!("{} <{}>", to_name, to_email).parse()?)
             .subject(subject)
-            .header(ContentType::TEXT_PLAIN)
-            .body(body)?;
+            .multipart(alternative_body(&template))?;
 
-        let response = self.mailer.send(email).await?;
+        let response = self.send_message(email).await?;
         Ok(response)
     }
 
@@ -247,26 +235,15 @@ The SOAR Team"#,
             get_staging_prefix(),
             device_address
         );
-        let body = format!(
-            r#"Hello {},
-
-An aircraft on your watchlist has completed a flight!
-
-Device: {}
-Flight Details: {}
-
-A KML file of the flight track is attached. You can open it in Google Earth or other mapping applications.
-
-Manage your watchlist and email preferences:
-{}
-
-Best regards,
-The SOAR Team"#,
-            to_name, device_address, flight_url, watchlist_url
-        );
+        let template = FlightCompletionTemplate {
+            to_name: to_name.to_string(),
+            device_address: device_address.to_string(),
+            flight_url,
+            watchlist_url,
+        };
 
         // Create KML attachment
-        use lettre::message::{Attachment, MultiPart, SinglePart};
+        use lettre::message::{Attachment, MultiPart};
 
         let kml_part = Attachment::new(kml_filename.to_string()).body(
             kml_content,
@@ -279,11 +256,11 @@ The SOAR Team"#,
             .subject(subject)
             .multipart(
                 MultiPart::mixed()
-                    .singlepart(SinglePart::plain(body))
+                    .multipart(alternative_body(&template))
                     .singlepart(kml_part),
             )?;
 
-        let response = self.mailer.send(email).await?;
+        let response = self.send_message(email).await?;
         Ok(response)
     }
 }
@@ -0,0 +1,216 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use lettre::address::Envelope;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{Message, SmtpTransport, Transport};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::Duration;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::email_reporter::EmailConfig;
+
+/// Backoff schedule (minutes), indexed by the number of failed sweep attempts so far: entry 0
+/// (immediate) is the first sweep after enqueueing, and after a failed sweep `attempts` is
+/// incremented and the entry at that index gives the next delay. Once `attempts` reaches the
+/// schedule's length there is no entry left and the message is dead-lettered.
+const RETRY_SCHEDULE_MINUTES: &[i64] = &[0, 5, 15, 60, 240, 720];
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SpooledEmailMeta {
+    id: String,
+    from: String,
+    recipients: Vec<String>,
+    subject: String,
+    first_enqueued_at: DateTime<Utc>,
+    attempts: u32,
+    next_retry_at: DateTime<Utc>,
+}
+
+/// Disk-backed spool for emails that failed to send over SMTP.
+///
+/// Each spooled message is stored as a pair of files sharing a UUID stem: `{id}.eml` holds
+/// the raw RFC822 bytes (via [`Message::formatted`]) and `{id}.json` holds a
+/// [`SpooledEmailMeta`] record. [`EmailSpool::sweep`] re-attempts delivery of anything whose
+/// `next_retry_at` has passed; there is no background daemon, so the sweep is driven by
+/// whatever cadence calls it (e.g. once per archive run).
+pub struct EmailSpool {
+    spool_dir: PathBuf,
+}
+
+impl EmailSpool {
+    pub fn new(spool_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            spool_dir: spool_dir.into(),
+        }
+    }
+
+    /// Spool directory read from `EMAIL_SPOOL_DIR`, defaulting to `spool/email`.
+    pub fn from_env() -> Self {
+        let dir = std::env::var("EMAIL_SPOOL_DIR").unwrap_or_else(|_| "spool/email".to_string());
+        Self::new(dir)
+    }
+
+    fn failed_dir(&self) -> PathBuf {
+        self.spool_dir.join("failed")
+    }
+
+    /// Serialize `message` to disk so delivery can be retried later.
+    pub fn enqueue(
+        &self,
+        message: &Message,
+        from: &str,
+        recipients: Vec<String>,
+        subject: &str,
+    ) -> Result<()> {
+        std::fs::create_dir_all(&self.spool_dir)
+            .context("Failed to create email spool directory")?;
+
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now();
+        let meta = SpooledEmailMeta {
+            id: id.clone(),
+            from: from.to_string(),
+            recipients,
+            subject: subject.to_string(),
+            first_enqueued_at: now,
+            attempts: 0,
+            next_retry_at: now,
+        };
+
+        self.write(&id, message.formatted(), &meta)?;
+
+        info!(
+            "Spooled email {} for retry after SMTP send failure (subject: {})",
+            id, meta.subject
+        );
+
+        Ok(())
+    }
+
+    fn write(&self, id: &str, raw: Vec<u8>, meta: &SpooledEmailMeta) -> Result<()> {
+        std::fs::write(self.eml_path(id), raw).context("Failed to write spooled email body")?;
+        std::fs::write(self.meta_path(id), serde_json::to_vec_pretty(meta)?)
+            .context("Failed to write spooled email metadata")?;
+        Ok(())
+    }
+
+    fn eml_path(&self, id: &str) -> PathBuf {
+        self.spool_dir.join(format!("{id}.eml"))
+    }
+
+    fn meta_path(&self, id: &str) -> PathBuf {
+        self.spool_dir.join(format!("{id}.json"))
+    }
+
+    /// Sweep the spool directory, attempting delivery of every message whose `next_retry_at`
+    /// has passed. Successes are removed; failures are rescheduled per
+    /// `RETRY_SCHEDULE_MINUTES`, and messages that exceed the schedule are moved to
+    /// `failed/` with a loud error instead of being retried forever.
+    pub fn sweep(&self, config: &EmailConfig) -> Result<()> {
+        if !self.spool_dir.exists() {
+            return Ok(());
+        }
+
+        let creds = Credentials::new(config.smtp_username.clone(), config.smtp_password.clone());
+        let mailer = SmtpTransport::relay(&config.smtp_server)?
+            .port(config.smtp_port)
+            .credentials(creds)
+            .timeout(Some(Duration::from_secs(30)))
+            .build();
+
+        for entry in std::fs::read_dir(&self.spool_dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+
+            let Some(id) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+
+            if let Err(e) = self.sweep_one(id, &mailer) {
+                warn!("Failed to sweep spooled email {}: {}", id, e);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn sweep_one(&self, id: &str, mailer: &SmtpTransport) -> Result<()> {
+        let meta_path = self.meta_path(id);
+        let mut meta: SpooledEmailMeta = serde_json::from_slice(
+            &std::fs::read(&meta_path).context("Failed to read spool metadata")?,
+        )
+        .context("Failed to parse spool metadata")?;
+
+        if meta.next_retry_at > Utc::now() {
+            return Ok(());
+        }
+
+        let raw = std::fs::read(self.eml_path(id)).context("Failed to read spooled email body")?;
+        let from: lettre::Address = meta.from.parse().context("Invalid spooled from address")?;
+        let to: Vec<lettre::Address> = meta
+            .recipients
+            .iter()
+            .map(|r| r.parse())
+            .collect::<std::result::Result<_, _>>()
+            .context("Invalid spooled recipient address")?;
+        let envelope = Envelope::new(Some(from), to).context("Failed to build spool envelope")?;
+
+        match mailer.send_raw(&envelope, &raw) {
+            Ok(_) => {
+                info!("Delivered spooled email {} (subject: {})", id, meta.subject);
+                std::fs::remove_file(&meta_path).ok();
+                std::fs::remove_file(self.eml_path(id)).ok();
+                Ok(())
+            }
+            Err(e) => {
+                meta.attempts += 1;
+                let delay_minutes = RETRY_SCHEDULE_MINUTES.get(meta.attempts as usize).copied();
+
+                match delay_minutes {
+                    Some(minutes) => {
+                        meta.next_retry_at = Utc::now() + chrono::Duration::minutes(minutes);
+                        std::fs::write(&meta_path, serde_json::to_vec_pretty(&meta)?)
+                            .context("Failed to update spool metadata")?;
+                        warn!(
+                            "Retry {} of spooled email {} failed, next attempt in {}m: {}",
+                            meta.attempts, id, minutes, e
+                        );
+                        Ok(())
+                    }
+                    None => {
+                        self.dead_letter(id, &meta)?;
+                        error!(
+                            "Spooled email {} (subject: {}) exceeded max retry attempts, moved to failed/: {}",
+                            id, meta.subject, e
+                        );
+                        Ok(())
+                    }
+                }
+            }
+        }
+    }
+
+    fn dead_letter(&self, id: &str, meta: &SpooledEmailMeta) -> Result<()> {
+        let failed_dir = self.failed_dir();
+        std::fs::create_dir_all(&failed_dir).context("Failed to create failed-email directory")?;
+
+        std::fs::write(
+            failed_dir.join(format!("{id}.json")),
+            serde_json::to_vec_pretty(meta)?,
+        )
+        .context("Failed to write failed-email metadata")?;
+        if let Ok(raw) = std::fs::read(self.eml_path(id)) {
+            std::fs::write(failed_dir.join(format!("{id}.eml")), raw)
+                .context("Failed to write failed-email body")?;
+        }
+
+        std::fs::remove_file(self.meta_path(id)).ok();
+        std::fs::remove_file(self.eml_path(id)).ok();
+
+        Ok(())
+    }
+}
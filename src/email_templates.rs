@@ -0,0 +1,241 @@
+//! Shared HTML layout and per-email templates for transactional mail
+//!
+//! Every transactional email implements [`MailTemplate`], which supplies both
+//! a plain-text body (the historical hand-formatted copy) and an HTML body so
+//! `EmailService` can send a `MultiPart::alternative` and let the receiving
+//! client pick whichever representation it renders best. HTML bodies share a
+//! single [`render_layout`] wrapper for the logo/footer/unsubscribe link so
+//! that's only defined once.
+
+fn base_url() -> String {
+    std::env::var("BASE_URL").unwrap_or_else(|_| "http://localhost:3000".to_string())
+}
+
+/// Escape a user-supplied value (pilot display name, device address, ...)
+/// before interpolating it into an HTML body, so it can't break out of the
+/// surrounding markup or inject content into a transactional email.
+fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+/// Wrap a template's inner HTML content in the shared SOAR layout: logo
+/// header, and a footer with a link to manage watchlist/email preferences.
+fn render_layout(content: &str) -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head><meta charset="utf-8"></head>
+<body style="margin:0;padding:0;background-color:#f4f5f7;font-family:-apple-system,Segoe UI,Roboto,Helvetica,Arial,sans-serif;color:#1a1a1a;">
+  <table role="presentation" width="100%" cellpadding="0" cellspacing="0" style="background-color:#f4f5f7;padding:24px 0;">
+    <tr><td align="center">
+      <table role="presentation" width="560" cellpadding="0" cellspacing="0" style="background-color:#ffffff;border-radius:8px;overflow:hidden;">
+        <tr><td style="background-color:#0b3d91;padding:20px 32px;">
+          <span style="color:#ffffff;font-size:20px;font-weight:bold;">SOAR</span>
+        </td></tr>
+        <tr><td style="padding:32px;">
+          {content}
+        </td></tr>
+        <tr><td style="padding:20px 32px;background-color:#f4f5f7;font-size:12px;color:#6b7280;">
+          <p style="margin:0 0 8px 0;">SOAR - Soaring Operations And Records</p>
+          <p style="margin:0;">
+            <a href="{base_url}/watchlist" style="color:#0b3d91;">Manage your watchlist and email preferences</a>
+          </p>
+        </td></tr>
+      </table>
+    </td></tr>
+  </table>
+</body>
+</html>"#,
+        content = content,
+        base_url = base_url(),
+    )
+}
+
+/// A renderable transactional email: a plain-text body and an HTML body.
+pub trait MailTemplate {
+    /// Plain-text body, used as the `text/plain` alternative.
+    fn text_body(&self) -> String;
+
+    /// Inner HTML content, rendered inside the shared layout.
+    fn html_content(&self) -> String;
+
+    /// Full HTML body (inner content wrapped in the shared layout), used as
+    /// the `text/html` alternative.
+    fn html_body(&self) -> String {
+        render_layout(&self.html_content())
+    }
+}
+
+pub struct PasswordResetTemplate {
+    pub to_name: String,
+    pub reset_url: String,
+}
+
+impl MailTemplate for PasswordResetTemplate {
+    fn text_body(&self) -> String {
+        format!(
+            r#"Hello {},
+
+We received a request to reset your password for your SOAR account.
+
+To reset your password, please click the following link:
+{}
+
+This link will expire in 1 hour for security reasons.
+
+If you did not request a password reset, please ignore this email and your password will remain unchanged.
+
+Best regards,
+The SOAR Team"#,
+            self.to_name, self.reset_url
+        )
+    }
+
+    fn html_content(&self) -> String {
+        format!(
+            r#"<p>Hello {},</p>
+<p>We received a request to reset your password for your SOAR account.</p>
+<p><a href="{}" style="display:inline-block;padding:10px 20px;background-color:#0b3d91;color:#ffffff;border-radius:4px;text-decoration:none;">Reset your password</a></p>
+<p style="font-size:13px;color:#6b7280;">This link will expire in 1 hour for security reasons.</p>
+<p style="font-size:13px;color:#6b7280;">If you did not request a password reset, please ignore this email and your password will remain unchanged.</p>"#,
+            escape_html(&self.to_name),
+            self.reset_url
+        )
+    }
+}
+
+pub struct EmailVerificationTemplate {
+    pub to_name: String,
+    pub verification_url: String,
+}
+
+impl MailTemplate for EmailVerificationTemplate {
+    fn text_body(&self) -> String {
+        format!(
+            r#"Hello {},
+
+Thank you for registering with SOAR! To complete your account setup, please verify your email address.
+
+Click the following link to verify your email:
+{}
+
+This link will expire in 24 hours for security reasons.
+
+If you did not create an account with SOAR, please ignore this email.
+
+Best regards,
+The SOAR Team"#,
+            self.to_name, self.verification_url
+        )
+    }
+
+    fn html_content(&self) -> String {
+        format!(
+            r#"<p>Hello {},</p>
+<p>Thank you for registering with SOAR! To complete your account setup, please verify your email address.</p>
+<p><a href="{}" style="display:inline-block;padding:10px 20px;background-color:#0b3d91;color:#ffffff;border-radius:4px;text-decoration:none;">Verify your email</a></p>
+<p style="font-size:13px;color:#6b7280;">This link will expire in 24 hours for security reasons.</p>
+<p style="font-size:13px;color:#6b7280;">If you did not create an account with SOAR, please ignore this email.</p>"#,
+            escape_html(&self.to_name),
+            self.verification_url
+        )
+    }
+}
+
+pub struct PilotInvitationTemplate {
+    pub to_name: String,
+    pub registration_url: String,
+}
+
+impl MailTemplate for PilotInvitationTemplate {
+    fn text_body(&self) -> String {
+        format!(
+            r#"Hello {},
+
+You've been added to your club's roster on SOAR! To access your account and manage your flight information, please complete your registration by setting a password.
+
+Click the following link to complete your registration:
+{}
+
+This link will expire in 72 hours for security reasons.
+
+Once you've set your password, you'll be able to:
+- View your flight history
+- Track your progress and achievements
+- Receive flight notifications
+- Access club information
+
+If you believe you received this email in error, please ignore it or contact your club administrator.
+
+Best regards,
+The SOAR Team"#,
+            self.to_name, self.registration_url
+        )
+    }
+
+    fn html_content(&self) -> String {
+        format!(
+            r#"<p>Hello {},</p>
+<p>You've been added to your club's roster on SOAR! To access your account and manage your flight information, please complete your registration by setting a password.</p>
+<p><a href="{}" style="display:inline-block;padding:10px 20px;background-color:#0b3d91;color:#ffffff;border-radius:4px;text-decoration:none;">Complete your registration</a></p>
+<p style="font-size:13px;color:#6b7280;">This link will expire in 72 hours for security reasons.</p>
+<p>Once you've set your password, you'll be able to:</p>
+<ul style="font-size:14px;">
+  <li>View your flight history</li>
+  <li>Track your progress and achievements</li>
+  <li>Receive flight notifications</li>
+  <li>Access club information</li>
+</ul>
+<p style="font-size:13px;color:#6b7280;">If you believe you received this email in error, please ignore it or contact your club administrator.</p>"#,
+            escape_html(&self.to_name),
+            self.registration_url
+        )
+    }
+}
+
+pub struct FlightCompletionTemplate {
+    pub to_name: String,
+    pub device_address: String,
+    pub flight_url: String,
+    pub watchlist_url: String,
+}
+
+impl MailTemplate for FlightCompletionTemplate {
+    fn text_body(&self) -> String {
+        format!(
+            r#"Hello {},
+
+An aircraft on your watchlist has completed a flight!
+
+Device: {}
+Flight Details: {}
+
+A KML file of the flight track is attached. You can open it in Google Earth or other mapping applications.
+
+Manage your watchlist and email preferences:
+{}
+
+Best regards,
+The SOAR Team"#,
+            self.to_name, self.device_address, self.flight_url, self.watchlist_url
+        )
+    }
+
+    fn html_content(&self) -> String {
+        format!(
+            r#"<p>Hello {},</p>
+<p>An aircraft on your watchlist has completed a flight!</p>
+<p><strong>Device:</strong> {}</p>
+<p><a href="{}" style="display:inline-block;padding:10px 20px;background-color:#0b3d91;color:#ffffff;border-radius:4px;text-decoration:none;">View flight details</a></p>
+<p style="font-size:13px;color:#6b7280;">A KML file of the flight track is attached. You can open it in Google Earth or other mapping applications.</p>"#,
+            escape_html(&self.to_name),
+            escape_html(&self.device_address),
+            self.flight_url
+        )
+    }
+}
@@ -3,7 +3,7 @@ use diesel::PgConnection;
 use diesel::r2d2::{ConnectionManager, Pool};
 use std::time::Duration;
 use tokio::time::interval;
-use tracing::{debug, error, info, warn};
+use tracing::{debug, error, info, warn, Instrument};
 use uuid::Uuid;
 
 use crate::airports_repo::AirportsRepository;
@@ -13,11 +13,15 @@ use crate::geocoding::Geocoder;
 use crate::locations::Location;
 use crate::locations_repo::LocationsRepository;
 
-/// TEMPORARY: Reverse geocoding is disabled for flight takeoffs and landings
-/// When this is false, the FlightLocationProcessor will not perform reverse geocoding
+/// TEMPORARY: Nominatim/Google reverse geocoding is disabled for flight takeoffs and landings
+/// When this is false, the FlightLocationProcessor will not fall back to the network geocoder
 /// This is a temporary measure to avoid unnecessary geocoding API calls
 const GEOCODING_ENABLED_FOR_FLIGHTS: bool = false;
 
+/// Radius (meters) within which a takeoff/landing coordinate is considered to be "at" the
+/// nearest airport, letting it resolve offline instead of falling back to the network geocoder
+const AIRPORT_RESOLUTION_RADIUS_METERS: f64 = 5_000.0;
+
 /// Background processor that adds location data to completed flights
 /// This runs periodically and processes flights that don't have location data yet
 pub struct FlightLocationProcessor {
@@ -40,34 +44,35 @@ impl FlightLocationProcessor {
     }
 
     /// Start the background processor that runs periodically
+    ///
+    /// The spawned task is named `flight-location-processor` so it's
+    /// identifiable in `tokio-console` when the opt-in runtime-introspection
+    /// layer is enabled.
     pub fn start(pool: Pool<ConnectionManager<PgConnection>>, interval_secs: u64) {
-        tokio::spawn(async move {
-            let processor = Self::new(pool);
-            let mut ticker = interval(Duration::from_secs(interval_secs));
-
-            info!(
-                "Started flight location processor (running every {} seconds)",
-                interval_secs
-            );
+        tokio::spawn(
+            async move {
+                let processor = Self::new(pool);
+                let mut ticker = interval(Duration::from_secs(interval_secs));
+
+                info!(
+                    "Started flight location processor (running every {} seconds)",
+                    interval_secs
+                );
 
-            loop {
-                ticker.tick().await;
+                loop {
+                    ticker.tick().await;
 
-                if let Err(e) = processor.process_flights_needing_locations().await {
-                    error!("Error processing flight locations: {}", e);
+                    if let Err(e) = processor.process_flights_needing_locations().await {
+                        error!("Error processing flight locations: {}", e);
+                    }
                 }
             }
-        });
+            .instrument(tracing::info_span!("flight-location-processor")),
+        );
     }
 
     /// Process a batch of flights that need location data
     async fn process_flights_needing_locations(&self) -> Result<()> {
-        // GEOCODING DISABLED: Skip processing if geocoding is disabled
-        if !GEOCODING_ENABLED_FOR_FLIGHTS {
-            debug!("Flight location geocoding is temporarily disabled, skipping");
-            return Ok(());
-        }
-
         // Get completed flights without location data (limit to 10 per batch to be nice to Nominatim)
         let flights = self
             .flights_repo
@@ -85,23 +90,32 @@ impl FlightLocationProcessor {
         );
 
         for flight in flights {
-            if let Err(e) = self.process_single_flight(&flight.id).await {
-                warn!(
-                    "Failed to process location data for flight {}: {}",
-                    flight.id, e
-                );
-            }
+            let used_network_geocoder = match self.process_single_flight(&flight.id).await {
+                Ok(used_network) => used_network,
+                Err(e) => {
+                    warn!(
+                        "Failed to process location data for flight {}: {}",
+                        flight.id, e
+                    );
+                    false
+                }
+            };
 
-            // Rate limiting: Nominatim allows max 1 request per second
-            // We do 2 requests per flight (takeoff + landing), so wait 2.5 seconds between flights
-            tokio::time::sleep(Duration::from_millis(2500)).await;
+            // Rate limiting: Nominatim allows max 1 request per second. Only throttle when we
+            // actually fell back to it - offline airport resolution runs at full batch speed.
+            if used_network_geocoder {
+                tokio::time::sleep(Duration::from_millis(2500)).await;
+            }
         }
 
         Ok(())
     }
 
     /// Process a single flight to add location data
-    async fn process_single_flight(&self, flight_id: &Uuid) -> Result<()> {
+    ///
+    /// Returns whether the network geocoder was used for either leg, so the caller can apply
+    /// Nominatim's rate limit only when it was actually hit.
+    async fn process_single_flight(&self, flight_id: &Uuid) -> Result<bool> {
         let flight = self
             .flights_repo
             .get_flight_by_id(*flight_id)
@@ -110,21 +124,21 @@ impl FlightLocationProcessor {
 
         // Skip if locations are already set
         if flight.takeoff_location_id.is_some() && flight.landing_location_id.is_some() {
-            return Ok(());
+            return Ok(false);
         }
 
         // Get takeoff coordinates from the first fix
-        let takeoff_location_id = if flight.takeoff_location_id.is_none() {
+        let (takeoff_location_id, takeoff_used_network) = if flight.takeoff_location_id.is_none() {
             self.get_or_create_takeoff_location(&flight).await?
         } else {
-            flight.takeoff_location_id
+            (flight.takeoff_location_id, false)
         };
 
         // Get landing coordinates from the last fix
-        let landing_location_id = if flight.landing_location_id.is_none() {
+        let (landing_location_id, landing_used_network) = if flight.landing_location_id.is_none() {
             self.get_or_create_landing_location(&flight).await?
         } else {
-            flight.landing_location_id
+            (flight.landing_location_id, false)
         };
 
         // Update the flight with location IDs
@@ -139,14 +153,14 @@ impl FlightLocationProcessor {
             );
         }
 
-        Ok(())
+        Ok(takeoff_used_network || landing_used_network)
     }
 
     /// Get or create location for takeoff
     async fn get_or_create_takeoff_location(
         &self,
         flight: &crate::flights::Flight,
-    ) -> Result<Option<Uuid>> {
+    ) -> Result<(Option<Uuid>, bool)> {
         // Get first fix for this flight to find takeoff coordinates
         let fixes = self
             .fixes_repo
@@ -158,18 +172,18 @@ impl FlightLocationProcessor {
             let longitude = first_fix.longitude;
 
             return self
-                .create_location_from_coordinates(latitude, longitude)
+                .resolve_location_for_coordinates(latitude, longitude)
                 .await;
         }
 
-        Ok(None)
+        Ok((None, false))
     }
 
     /// Get or create location for landing
     async fn get_or_create_landing_location(
         &self,
         flight: &crate::flights::Flight,
-    ) -> Result<Option<Uuid>> {
+    ) -> Result<(Option<Uuid>, bool)> {
         // Get last fix for this flight to find landing coordinates
         // We need to get all fixes and take the last one since there's no direct "last fix" query
         let fixes = self
@@ -182,15 +196,97 @@ impl FlightLocationProcessor {
             let longitude = last_fix.longitude;
 
             return self
-                .create_location_from_coordinates(latitude, longitude)
+                .resolve_location_for_coordinates(latitude, longitude)
                 .await;
         }
 
-        Ok(None)
+        Ok((None, false))
+    }
+
+    /// Resolve a takeoff/landing coordinate to a location, preferring the nearest known
+    /// airport within `AIRPORT_RESOLUTION_RADIUS_METERS` so the common case resolves offline
+    /// at full batch speed. Only falls back to the network geocoder (and only when
+    /// `GEOCODING_ENABLED_FOR_FLIGHTS` is set) if no airport is within range.
+    ///
+    /// Returns the resolved location id alongside whether the network geocoder was used, so
+    /// the caller can apply Nominatim's rate limit only when it was actually hit.
+    async fn resolve_location_for_coordinates(
+        &self,
+        latitude: f64,
+        longitude: f64,
+    ) -> Result<(Option<Uuid>, bool)> {
+        if let Some(location_id) = self
+            .resolve_nearest_airport_location(latitude, longitude)
+            .await?
+        {
+            return Ok((Some(location_id), false));
+        }
+
+        if !GEOCODING_ENABLED_FOR_FLIGHTS {
+            return Ok((None, false));
+        }
+
+        let location_id = self
+            .create_location_from_coordinates(latitude, longitude)
+            .await?;
+
+        Ok((location_id, true))
+    }
+
+    /// Try to resolve a coordinate to the nearest known airport within
+    /// `AIRPORT_RESOLUTION_RADIUS_METERS`, building a `Location` from the airport's
+    /// municipality/region/country instead of performing a network reverse geocode.
+    async fn resolve_nearest_airport_location(
+        &self,
+        latitude: f64,
+        longitude: f64,
+    ) -> Result<Option<Uuid>> {
+        let nearest = self
+            .airports_repo
+            .find_nearest_airports(latitude, longitude, AIRPORT_RESOLUTION_RADIUS_METERS, 1)
+            .await?;
+
+        let Some((airport, distance_meters)) = nearest.into_iter().next() else {
+            return Ok(None);
+        };
+
+        debug!(
+            "Resolved ({}, {}) to airport {} ({:.0}m away)",
+            latitude, longitude, airport.ident, distance_meters
+        );
+
+        match self
+            .locations_repo
+            .find_or_create(
+                None, // street1
+                None, // street2
+                airport.municipality.clone(),
+                airport.iso_region.clone(),
+                None, // zip_code
+                None, // region_code
+                airport.iso_country.clone(),
+                Some(crate::locations::Point::new(latitude, longitude)),
+            )
+            .await
+        {
+            Ok(created_location) => {
+                info!(
+                    "Created/found location {} for coordinates ({}, {}) via nearest airport {}",
+                    created_location.id, latitude, longitude, airport.ident
+                );
+                Ok(Some(created_location.id))
+            }
+            Err(e) => {
+                warn!(
+                    "Failed to create location for airport {} at ({}, {}): {}",
+                    airport.ident, latitude, longitude, e
+                );
+                Ok(None)
+            }
+        }
     }
 
     /// Create a location from coordinates using reverse geocoding
-    /// Note: This method is not called when GEOCODING_ENABLED_FOR_FLIGHTS is false
     async fn create_location_from_coordinates(
         &self,
         latitude: f64,
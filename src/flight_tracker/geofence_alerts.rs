@@ -9,112 +9,133 @@ use uuid::Uuid;
 
 use crate::Fix;
 use crate::email::{AircraftEmailData, EmailService, GeofenceExitEmailData};
-use crate::geofence::{Geofence, GeofenceExitEvent, GeofenceLayer};
+use crate::geofence::{Geofence, GeofenceExitEvent, NotificationChannel};
 use crate::geofence_repo::GeofenceRepository;
+use crate::ogn_aprs_aircraft::AdsbEmitterCategory;
 use crate::users_repo::UsersRepository;
 
-use super::aircraft_state::GeofenceStatus;
-use super::geofence_detector::{check_fix_against_geofence, has_exited_geofence, is_inside};
+use super::geofence_debounce::{ConfirmedTransition, GeofenceDebouncer};
+use super::geofence_detector::check_fix_against_geofence;
 
-/// Check a fix against all geofences for an aircraft and detect exits
+/// Check a fix against all geofences for an aircraft, feed each check through
+/// `debouncer` and persist whatever transition it confirms: an entry event
+/// the first time the aircraft is confirmed inside, or an exit event once
+/// the debounced exit is confirmed.
 ///
-/// Returns a list of exit events that should be recorded and alerted.
+/// Returns the geofences (alongside their newly created exit events) that the
+/// debouncer confirmed an exit for, so `process_geofence_exits` can alert
+/// subscribers. Confirmed entries are only recorded (for dwell-time
+/// correlation via `GeofenceRepository::get_dwell_intervals_for_flight`),
+/// not alerted on.
 pub async fn check_geofences_for_aircraft(
     fix: &Fix,
-    previous_status: &GeofenceStatus,
+    debouncer: &mut GeofenceDebouncer,
     geofence_repo: &GeofenceRepository,
-) -> Result<(Vec<(Geofence, GeofenceLayer)>, GeofenceStatus)> {
+    aircraft_category: Option<AdsbEmitterCategory>,
+) -> Result<Vec<(Geofence, GeofenceExitEvent)>> {
     // Get all geofences linked to this aircraft
     let geofences = geofence_repo
         .get_geofences_for_aircraft(fix.aircraft_id)
         .await?;
 
     if geofences.is_empty() {
-        return Ok((vec![], GeofenceStatus::new()));
+        return Ok(vec![]);
     }
 
+    let Some(flight_id) = fix.flight_id else {
+        warn!(
+            "Cannot check geofences for aircraft {} - no flight_id on fix",
+            fix.aircraft_id
+        );
+        return Ok(vec![]);
+    };
+
     let mut exits = Vec::new();
-    let mut new_status = GeofenceStatus::new();
 
     for geofence in geofences {
-        let result = check_fix_against_geofence(fix, &geofence);
-        let currently_inside = is_inside(&result);
+        let check = check_fix_against_geofence(fix, &geofence);
 
-        // Check for exit transition
-        let was_inside = previous_status.get(&geofence.id).copied().unwrap_or(false);
-
-        if let Some(exited_layer) = has_exited_geofence(was_inside, &result) {
-            info!(
-                "Aircraft {} exited geofence '{}' (layer: {}-{} ft, {} nm)",
+        let transition = match debouncer
+            .observe_and_persist(
+                geofence_repo,
+                &geofence,
                 fix.aircraft_id,
-                geofence.name,
-                exited_layer.floor_ft,
-                exited_layer.ceiling_ft,
-                exited_layer.radius_nm
-            );
-            exits.push((geofence.clone(), exited_layer));
-        }
+                flight_id,
+                fix.received_at,
+                (fix.latitude, fix.longitude),
+                fix.altitude_msl_feet,
+                aircraft_category,
+                &check,
+            )
+            .await
+        {
+            Ok(transition) => transition,
+            Err(e) => {
+                error!(
+                    "Failed to persist geofence transition for geofence {}: {}",
+                    geofence.id, e
+                );
+                continue;
+            }
+        };
 
-        // Update status for next check
-        new_status.insert(geofence.id, currently_inside);
+        match transition {
+            Some(ConfirmedTransition::Entered(event)) => {
+                info!(
+                    "Aircraft {} entered geofence '{}' (layer: {}-{} ft, {} nm)",
+                    fix.aircraft_id,
+                    geofence.name,
+                    event.entry_layer.floor_ft,
+                    event.entry_layer.ceiling_ft,
+                    event.entry_layer.radius_nm
+                );
+                metrics::counter!("geofence.entry_events_created_total").increment(1);
+            }
+            Some(ConfirmedTransition::Exited(event)) => {
+                info!(
+                    "Aircraft {} exited geofence '{}' (layer: {}-{} ft, {} nm)",
+                    fix.aircraft_id,
+                    geofence.name,
+                    event.exit_layer.floor_ft,
+                    event.exit_layer.ceiling_ft,
+                    event.exit_layer.radius_nm
+                );
+                metrics::counter!("geofence.exit_events_created_total").increment(1);
+                exits.push((geofence, event));
+            }
+            None => {}
+        }
     }
 
-    Ok((exits, new_status))
+    Ok(exits)
 }
 
-/// Process geofence exits: create events and send alerts
+/// Process confirmed geofence exits: queue non-email deliveries and send
+/// email alerts to subscribers.
 ///
-/// This is called when exits are detected. It:
-/// 1. Creates exit event records in the database
-/// 2. Sends email alerts to all subscribers
-#[allow(clippy::too_many_arguments)]
+/// Exit events have already been recorded (and gated by the geofence's
+/// altitude ceiling/category blocklist) by
+/// `GeofenceDebouncer::observe_and_persist` in
+/// `check_geofences_for_aircraft`.
 pub async fn process_geofence_exits(
-    fix: &Fix,
-    exits: Vec<(Geofence, GeofenceLayer)>,
+    exits: Vec<(Geofence, GeofenceExitEvent)>,
     geofence_repo: &GeofenceRepository,
     users_repo: &UsersRepository,
     aircraft_registration: Option<String>,
     aircraft_model: String,
     hex_address: String,
+    aircraft_id: Uuid,
 ) {
-    let flight_id = match fix.flight_id {
-        Some(id) => id,
-        None => {
-            warn!(
-                "Cannot process geofence exit for aircraft {} - no flight_id on fix",
-                fix.aircraft_id
+    for (geofence, event) in exits {
+        // Queue webhook/SMS/push deliveries for this exit event. Email is
+        // still sent directly (not queued) by `send_geofence_exit_alerts`
+        // below.
+        if let Err(e) = enqueue_non_email_notifications(geofence_repo, &event, &geofence).await {
+            error!(
+                "Failed to enqueue notification jobs for exit event {}: {}",
+                event.id, e
             );
-            return;
         }
-    };
-
-    for (geofence, exited_layer) in exits {
-        // Create exit event record
-        let event = match geofence_repo
-            .create_exit_event(
-                geofence.id,
-                flight_id,
-                fix.aircraft_id,
-                fix.received_at,
-                fix.latitude,
-                fix.longitude,
-                fix.altitude_msl_feet,
-                &exited_layer,
-            )
-            .await
-        {
-            Ok(event) => {
-                metrics::counter!("geofence.exit_events_created_total").increment(1);
-                event
-            }
-            Err(e) => {
-                error!(
-                    "Failed to create geofence exit event for geofence {}: {}",
-                    geofence.id, e
-                );
-                continue;
-            }
-        };
 
         // Send email alerts in background
         let geofence_repo_clone = geofence_repo.clone();
@@ -122,13 +143,11 @@ pub async fn process_geofence_exits(
         let aircraft_registration = aircraft_registration.clone();
         let aircraft_model = aircraft_model.clone();
         let hex_address = hex_address.clone();
-        let aircraft_id = fix.aircraft_id;
 
         tokio::spawn(async move {
             send_geofence_exit_alerts(
                 event,
                 geofence,
-                exited_layer,
                 geofence_repo_clone,
                 users_repo_clone,
                 aircraft_id,
@@ -141,12 +160,68 @@ pub async fn process_geofence_exits(
     }
 }
 
+/// Enqueue a notification job for every subscriber of `geofence` whose
+/// channel isn't email (email is delivered directly, not queued). Webhook
+/// jobs carry the destination URL from `channel_config` plus a JSON payload
+/// describing the exit; SMS/push jobs just carry the event ID, since
+/// `process_next_notification_job` treats those channels as already handled
+/// once claimed (no SMS/push sender exists yet).
+async fn enqueue_non_email_notifications(
+    geofence_repo: &GeofenceRepository,
+    event: &GeofenceExitEvent,
+    geofence: &Geofence,
+) -> Result<()> {
+    let subscribers = geofence_repo.get_subscribers(geofence.id).await?;
+
+    for subscriber in subscribers {
+        let job = match subscriber.channel {
+            NotificationChannel::Email => continue,
+            NotificationChannel::Webhook => {
+                let url = subscriber
+                    .channel_config
+                    .as_ref()
+                    .and_then(|config| config.get("url"))
+                    .and_then(|url| url.as_str());
+                let Some(url) = url else {
+                    warn!(
+                        "Webhook subscriber {} for geofence {} has no configured url, skipping",
+                        subscriber.user_id, geofence.id
+                    );
+                    continue;
+                };
+                serde_json::json!({
+                    "url": url,
+                    "payload": {
+                        "geofence_id": geofence.id,
+                        "geofence_name": geofence.name,
+                        "exit_event_id": event.id,
+                        "flight_id": event.flight_id,
+                        "aircraft_id": event.aircraft_id,
+                        "exit_time": event.exit_time,
+                        "exit_latitude": event.exit_latitude,
+                        "exit_longitude": event.exit_longitude,
+                        "exit_altitude_msl_ft": event.exit_altitude_msl_ft,
+                    },
+                })
+            }
+            NotificationChannel::Sms | NotificationChannel::Push => {
+                serde_json::json!({ "exit_event_id": event.id })
+            }
+        };
+
+        geofence_repo
+            .enqueue_notification_job(event.id, subscriber.user_id, subscriber.channel, job)
+            .await?;
+    }
+
+    Ok(())
+}
+
 /// Send email alerts for a geofence exit event
 #[allow(clippy::too_many_arguments)]
 async fn send_geofence_exit_alerts(
     event: GeofenceExitEvent,
     geofence: Geofence,
-    exited_layer: GeofenceLayer,
     geofence_repo: GeofenceRepository,
     users_repo: UsersRepository,
     aircraft_id: Uuid,
@@ -194,9 +269,9 @@ async fn send_geofence_exit_alerts(
         exit_latitude: event.exit_latitude,
         exit_longitude: event.exit_longitude,
         exit_altitude_msl_ft: event.exit_altitude_msl_ft,
-        exit_layer_floor_ft: exited_layer.floor_ft,
-        exit_layer_ceiling_ft: exited_layer.ceiling_ft,
-        exit_layer_radius_nm: exited_layer.radius_nm,
+        exit_layer_floor_ft: event.exit_layer.floor_ft,
+        exit_layer_ceiling_ft: event.exit_layer.ceiling_ft,
+        exit_layer_radius_nm: event.exit_layer.radius_nm,
     };
 
     let mut emails_sent = 0;
@@ -241,7 +316,7 @@ async fn send_geofence_exit_alerts(
     // Update exit event with count of emails sent
     if emails_sent > 0 {
         if let Err(e) = geofence_repo
-            .update_exit_event_email_count(event.id, emails_sent)
+            .set_channel_delivery_count(event.id, NotificationChannel::Email, emails_sent)
             .await
         {
             error!(
@@ -256,3 +331,92 @@ async fn send_geofence_exit_alerts(
         );
     }
 }
+
+/// POST a webhook delivery job's payload to its configured URL. Returns
+/// `Err` on any non-2xx response or transport failure so the caller can
+/// reschedule the job with `GeofenceRepository::fail_job`'s backoff.
+async fn deliver_webhook(
+    client: &reqwest::Client,
+    url: &str,
+    payload: &serde_json::Value,
+) -> Result<()> {
+    let response = client
+        .post(url)
+        .json(payload)
+        .timeout(std::time::Duration::from_secs(10))
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        anyhow::bail!(
+            "webhook delivery to {} failed with status {}",
+            url,
+            response.status()
+        );
+    }
+
+    Ok(())
+}
+
+/// Claim and attempt delivery of the next queued notification job, for
+/// whichever channel it targets. On success the job is completed and
+/// removed from the queue; on failure it's rescheduled via `fail_job`'s
+/// exponential backoff, up to `max_attempts`. Returns `Ok(false)` if the
+/// queue was empty, so a caller can loop until drained.
+pub async fn process_next_notification_job(
+    geofence_repo: &GeofenceRepository,
+    webhook_client: &reqwest::Client,
+    max_attempts: i32,
+) -> Result<bool> {
+    let Some(job) = geofence_repo.claim_next_job().await? else {
+        return Ok(false);
+    };
+
+    let result: Result<()> = match job.channel {
+        NotificationChannel::Webhook => match job.job.get("url").and_then(|v| v.as_str()) {
+            Some(url) => {
+                let payload = job.job.get("payload").unwrap_or(&job.job);
+                deliver_webhook(webhook_client, url, payload).await
+            }
+            None => Err(anyhow::anyhow!("webhook job {} missing url", job.id)),
+        },
+        // Email is delivered directly by `process_geofence_exits`/
+        // `send_geofence_exit_alerts` rather than through the queue, and there
+        // is no SMS/push sender; a queued job for any of these channels is
+        // completed without a delivery attempt.
+        NotificationChannel::Email | NotificationChannel::Sms | NotificationChannel::Push => {
+            Ok(())
+        }
+    };
+
+    match result {
+        Ok(()) => geofence_repo.complete_job(job.id).await?,
+        Err(e) => {
+            warn!("Notification job {} failed: {}", job.id, e);
+            geofence_repo.fail_job(job.id, max_attempts).await?;
+        }
+    }
+
+    Ok(true)
+}
+
+/// Background task that drains `geofence_notification_jobs` by repeatedly
+/// claiming and delivering the next job, sleeping between polls once the
+/// queue runs dry. Modeled on
+/// `crate::actions::stripe_connect::stripe_webhook_retry_task`.
+pub async fn geofence_notification_job_poller(
+    geofence_repo: GeofenceRepository,
+    webhook_client: reqwest::Client,
+    max_attempts: i32,
+) {
+    loop {
+        match process_next_notification_job(&geofence_repo, &webhook_client, max_attempts).await {
+            Ok(true) => continue,
+            Ok(false) => tokio::time::sleep(std::time::Duration::from_secs(10)).await,
+            Err(e) => {
+                warn!("Geofence notification job poll failed: {:#}", e);
+                tokio::time::sleep(std::time::Duration::from_secs(10)).await;
+            }
+        }
+    }
+}
@@ -0,0 +1,366 @@
+//! Debounce state machine for geofence exit detection
+//!
+//! A noisy ADS-B/OGN track can flicker back and forth across a layer's
+//! boundary fix-to-fix, which would otherwise cause
+//! [`process_geofence_exits`](super::geofence_alerts::process_geofence_exits)
+//! to raise a storm of spurious exit events. This module sits in front of
+//! `GeofenceRepository::create_exit_event` and only confirms an exit once
+//! `confirm_after_fixes` consecutive fixes land outside the layer.
+//!
+//! Modeled on heliwatch's Appeared/Moved/Disappeared/Ignored track
+//! transitions: each fix advances a small per-`(geofence, aircraft)` state
+//! machine rather than firing off a single raw containment check. A gap
+//! longer than `state_timeout` (heliwatch uses 180s) is treated as the
+//! track going stale - the state resets silently instead of firing an exit.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::geofence::{Geofence, GeofenceEntryEvent, GeofenceExitEvent, GeofenceLayer};
+use crate::geofence_repo::GeofenceRepository;
+use crate::ogn_aprs_aircraft::AdsbEmitterCategory;
+
+use super::geofence_detector::{is_inside, GeofenceCheckResult};
+
+/// Tuning for the debounce state machine.
+#[derive(Debug, Clone, Copy)]
+pub struct DebounceConfig {
+    /// Number of consecutive outside-layer fixes required before an exit is confirmed.
+    pub confirm_after_fixes: u32,
+    /// A gap between fixes longer than this closes the track as stale instead
+    /// of counting toward `confirm_after_fixes`.
+    pub state_timeout: Duration,
+}
+
+impl Default for DebounceConfig {
+    fn default() -> Self {
+        Self {
+            confirm_after_fixes: 3,
+            state_timeout: Duration::from_secs(180),
+        }
+    }
+}
+
+/// Outcome of feeding one fix into the debounce state machine.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Transition {
+    /// First fix confirming the aircraft is inside the geofence.
+    Appeared,
+    /// The track is continuing: still inside, or accumulating outside fixes
+    /// below the confirmation threshold.
+    Moved,
+    /// `confirm_after_fixes` consecutive outside fixes were observed - the
+    /// exit is confirmed.
+    ExitConfirmed { exited_layer: GeofenceLayer },
+    /// The track went stale (gap exceeded `state_timeout`) and was reset.
+    /// No exit should be reported for the closed track.
+    Disappeared,
+    /// Nothing notable: e.g. an outside fix for a track that was never
+    /// confirmed inside, or an indeterminate check (missing altitude / no
+    /// layer at altitude) that doesn't move the state machine.
+    Ignored,
+}
+
+struct TrackState {
+    currently_inside: bool,
+    last_fix_time: DateTime<Utc>,
+    last_position: (f64, f64),
+    consecutive_outside_fixes: u32,
+}
+
+/// In-memory debounce state for every `(geofence_id, aircraft_id)` pair this
+/// process has observed. Not persisted - a process restart just means a
+/// short re-confirmation window, an acceptable tradeoff for a cache whose
+/// whole purpose is smoothing fix-to-fix jitter.
+pub struct GeofenceDebouncer {
+    config: DebounceConfig,
+    tracks: HashMap<(Uuid, Uuid), TrackState>,
+}
+
+impl Default for GeofenceDebouncer {
+    fn default() -> Self {
+        Self::new(DebounceConfig::default())
+    }
+}
+
+impl GeofenceDebouncer {
+    pub fn new(config: DebounceConfig) -> Self {
+        Self {
+            config,
+            tracks: HashMap::new(),
+        }
+    }
+
+    /// Feed one fix's containment check result into the state machine for
+    /// `(geofence_id, aircraft_id)` and decide what, if anything, changed.
+    pub fn observe(
+        &mut self,
+        geofence_id: Uuid,
+        aircraft_id: Uuid,
+        fix_time: DateTime<Utc>,
+        position: (f64, f64),
+        check: &GeofenceCheckResult,
+    ) -> Transition {
+        let key = (geofence_id, aircraft_id);
+
+        let timed_out = self.tracks.get(&key).is_some_and(|track| {
+            fix_time.signed_duration_since(track.last_fix_time)
+                > chrono::Duration::from_std(self.config.state_timeout).unwrap_or_default()
+        });
+        if timed_out {
+            self.tracks.remove(&key);
+        }
+
+        match self.tracks.get_mut(&key) {
+            None => {
+                let currently_inside = is_inside(check);
+                self.tracks.insert(
+                    key,
+                    TrackState {
+                        currently_inside,
+                        last_fix_time: fix_time,
+                        last_position: position,
+                        consecutive_outside_fixes: 0,
+                    },
+                );
+                if timed_out {
+                    Transition::Disappeared
+                } else if currently_inside {
+                    Transition::Appeared
+                } else {
+                    Transition::Ignored
+                }
+            }
+            Some(track) => {
+                track.last_fix_time = fix_time;
+                track.last_position = position;
+
+                match check {
+                    GeofenceCheckResult::Outside { exited_layer } if track.currently_inside => {
+                        track.consecutive_outside_fixes += 1;
+                        if track.consecutive_outside_fixes >= self.config.confirm_after_fixes {
+                            track.currently_inside = false;
+                            track.consecutive_outside_fixes = 0;
+                            Transition::ExitConfirmed {
+                                exited_layer: exited_layer.clone(),
+                            }
+                        } else {
+                            Transition::Moved
+                        }
+                    }
+                    GeofenceCheckResult::Inside { .. } => {
+                        let was_inside = track.currently_inside;
+                        track.currently_inside = true;
+                        track.consecutive_outside_fixes = 0;
+                        if was_inside {
+                            Transition::Moved
+                        } else {
+                            Transition::Appeared
+                        }
+                    }
+                    _ => Transition::Ignored,
+                }
+            }
+        }
+    }
+
+    /// Remove all debounce state for a track, e.g. once a flight has landed
+    /// and is no longer being monitored for geofence exits.
+    pub fn clear(&mut self, geofence_id: Uuid, aircraft_id: Uuid) {
+        self.tracks.remove(&(geofence_id, aircraft_id));
+    }
+
+    /// Feed `check` through [`Self::observe`] and persist whatever the
+    /// debounced transition confirms: an [`GeofenceEntryEvent`] the first
+    /// time the track is confirmed inside (`Transition::Appeared`), or a
+    /// [`GeofenceExitEvent`] once `confirm_after_fixes` consecutive outside
+    /// fixes land and `geofence`'s altitude ceiling/category blocklist (see
+    /// [`Geofence::allows_exit_alert`]) still allow alerting on it. Returns
+    /// `Ok(None)` for every other transition, including a confirmed exit
+    /// that's filtered out by those gates.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn observe_and_persist(
+        &mut self,
+        repo: &GeofenceRepository,
+        geofence: &Geofence,
+        aircraft_id: Uuid,
+        flight_id: Uuid,
+        fix_time: DateTime<Utc>,
+        position: (f64, f64),
+        altitude_msl_ft: Option<i32>,
+        aircraft_category: Option<AdsbEmitterCategory>,
+        check: &GeofenceCheckResult,
+    ) -> Result<Option<ConfirmedTransition>> {
+        let transition = self.observe(geofence.id, aircraft_id, fix_time, position, check);
+
+        match transition {
+            Transition::Appeared => {
+                let GeofenceCheckResult::Inside { layer } = check else {
+                    return Ok(None);
+                };
+                let event = repo
+                    .create_entry_event(
+                        geofence.id,
+                        flight_id,
+                        aircraft_id,
+                        fix_time,
+                        position.0,
+                        position.1,
+                        altitude_msl_ft,
+                        layer,
+                    )
+                    .await?;
+                Ok(Some(ConfirmedTransition::Entered(event)))
+            }
+            Transition::ExitConfirmed { exited_layer } => {
+                if !geofence.allows_exit_alert(altitude_msl_ft, aircraft_category) {
+                    return Ok(None);
+                }
+
+                let event = repo
+                    .create_exit_event(
+                        geofence.id,
+                        flight_id,
+                        aircraft_id,
+                        fix_time,
+                        position.0,
+                        position.1,
+                        altitude_msl_ft,
+                        aircraft_category.and_then(|c| c.numeric_code()),
+                        &exited_layer,
+                    )
+                    .await?;
+
+                Ok(Some(ConfirmedTransition::Exited(event)))
+            }
+            Transition::Moved | Transition::Disappeared | Transition::Ignored => Ok(None),
+        }
+    }
+}
+
+/// A debounced transition that was confirmed and persisted by
+/// [`GeofenceDebouncer::observe_and_persist`].
+#[derive(Debug, Clone)]
+pub enum ConfirmedTransition {
+    Entered(GeofenceEntryEvent),
+    Exited(GeofenceExitEvent),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layer() -> GeofenceLayer {
+        GeofenceLayer::new(0, 5000, 5.0)
+    }
+
+    fn inside() -> GeofenceCheckResult {
+        GeofenceCheckResult::Inside { layer: layer() }
+    }
+
+    fn outside() -> GeofenceCheckResult {
+        GeofenceCheckResult::Outside {
+            exited_layer: layer(),
+        }
+    }
+
+    #[test]
+    fn first_inside_fix_is_appeared() {
+        let mut debouncer = GeofenceDebouncer::default();
+        let transition = debouncer.observe(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Utc::now(),
+            (0.0, 0.0),
+            &inside(),
+        );
+        assert_eq!(transition, Transition::Appeared);
+    }
+
+    #[test]
+    fn first_outside_fix_is_ignored() {
+        let mut debouncer = GeofenceDebouncer::default();
+        let transition = debouncer.observe(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Utc::now(),
+            (0.0, 0.0),
+            &outside(),
+        );
+        assert_eq!(transition, Transition::Ignored);
+    }
+
+    #[test]
+    fn exit_requires_consecutive_outside_fixes() {
+        let geofence_id = Uuid::new_v4();
+        let aircraft_id = Uuid::new_v4();
+        let mut debouncer = GeofenceDebouncer::new(DebounceConfig {
+            confirm_after_fixes: 3,
+            state_timeout: Duration::from_secs(180),
+        });
+        let t0 = Utc::now();
+
+        assert_eq!(
+            debouncer.observe(geofence_id, aircraft_id, t0, (0.0, 0.0), &inside()),
+            Transition::Appeared
+        );
+        assert_eq!(
+            debouncer.observe(geofence_id, aircraft_id, t0, (0.0, 0.0), &outside()),
+            Transition::Moved
+        );
+        assert_eq!(
+            debouncer.observe(geofence_id, aircraft_id, t0, (0.0, 0.0), &outside()),
+            Transition::Moved
+        );
+        assert_eq!(
+            debouncer.observe(geofence_id, aircraft_id, t0, (0.0, 0.0), &outside()),
+            Transition::ExitConfirmed {
+                exited_layer: layer()
+            }
+        );
+    }
+
+    #[test]
+    fn flicker_back_inside_resets_the_outside_counter() {
+        let geofence_id = Uuid::new_v4();
+        let aircraft_id = Uuid::new_v4();
+        let mut debouncer = GeofenceDebouncer::new(DebounceConfig {
+            confirm_after_fixes: 2,
+            state_timeout: Duration::from_secs(180),
+        });
+        let t0 = Utc::now();
+
+        debouncer.observe(geofence_id, aircraft_id, t0, (0.0, 0.0), &inside());
+        debouncer.observe(geofence_id, aircraft_id, t0, (0.0, 0.0), &outside());
+        // Back inside before the threshold is reached - should not confirm an exit.
+        assert_eq!(
+            debouncer.observe(geofence_id, aircraft_id, t0, (0.0, 0.0), &inside()),
+            Transition::Moved
+        );
+        assert_eq!(
+            debouncer.observe(geofence_id, aircraft_id, t0, (0.0, 0.0), &outside()),
+            Transition::Moved
+        );
+    }
+
+    #[test]
+    fn stale_gap_closes_the_track_without_firing_an_exit() {
+        let geofence_id = Uuid::new_v4();
+        let aircraft_id = Uuid::new_v4();
+        let mut debouncer = GeofenceDebouncer::new(DebounceConfig {
+            confirm_after_fixes: 1,
+            state_timeout: Duration::from_secs(180),
+        });
+        let t0 = Utc::now();
+
+        debouncer.observe(geofence_id, aircraft_id, t0, (0.0, 0.0), &inside());
+
+        let t1 = t0 + chrono::Duration::seconds(300);
+        let transition = debouncer.observe(geofence_id, aircraft_id, t1, (0.0, 0.0), &outside());
+        assert_eq!(transition, Transition::Disappeared);
+    }
+}
@@ -1,6 +1,10 @@
+mod aircraft_state;
 mod aircraft_tracker;
 pub mod altitude;
 mod flight_lifecycle;
+pub(crate) mod geofence_alerts;
+mod geofence_debounce;
+mod geofence_detector;
 mod geometry;
 mod location;
 mod runway;
@@ -4,10 +4,13 @@
 //! similar to Class B airspace. Each layer has its own radius from the center point.
 
 use chrono::{DateTime, Utc};
+use diesel_derive_enum::DbEnum;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 use uuid::Uuid;
 
+use crate::ogn_aprs_aircraft::AdsbEmitterCategory;
+
 /// A single altitude layer with its radius
 /// Altitudes are MSL (Mean Sea Level) in feet
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
@@ -55,12 +58,46 @@ pub struct Geofence {
     pub center_longitude: f64,
     pub max_radius_meters: f64,
     pub layers: Vec<GeofenceLayer>,
+    /// Exit events above this altitude (MSL feet) are not recorded or
+    /// alerted on, e.g. so a glider club geofence can ignore airliners
+    /// overflying at cruise altitude. `None` means no ceiling.
+    pub max_altitude_msl_ft: Option<i32>,
+    /// ADS-B emitter category codes (see [`AdsbEmitterCategory::numeric_code`])
+    /// to exclude from exit events, e.g. `[2, 3, 4, 5]` to drop
+    /// Small/Large/High-Vortex-Large/Heavy traffic.
+    pub ignored_categories: Vec<i16>,
     pub owner_user_id: Uuid,
     pub club_id: Option<Uuid>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+impl Geofence {
+    /// Whether an exit at `altitude_msl_ft` by an aircraft of `category`
+    /// should be recorded and alerted on, per this geofence's altitude
+    /// ceiling and category blocklist. Missing altitude/category data can't
+    /// be matched against either filter, so it passes through unfiltered.
+    pub fn allows_exit_alert(
+        &self,
+        altitude_msl_ft: Option<i32>,
+        category: Option<AdsbEmitterCategory>,
+    ) -> bool {
+        if let (Some(max_altitude), Some(altitude)) = (self.max_altitude_msl_ft, altitude_msl_ft) {
+            if altitude > max_altitude {
+                return false;
+            }
+        }
+
+        if let Some(code) = category.and_then(|c| c.numeric_code()) {
+            if self.ignored_categories.contains(&code) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
 /// Request to create a new geofence
 #[derive(Debug, Deserialize, TS)]
 #[ts(export, export_to = "../web/src/lib/types/generated/")]
@@ -71,6 +108,9 @@ pub struct CreateGeofenceRequest {
     pub center_latitude: f64,
     pub center_longitude: f64,
     pub layers: Vec<GeofenceLayer>,
+    pub max_altitude_msl_ft: Option<i32>,
+    #[serde(default)]
+    pub ignored_categories: Vec<i16>,
     pub club_id: Option<Uuid>,
 }
 
@@ -125,6 +165,21 @@ pub struct UpdateGeofenceRequest {
     pub center_latitude: Option<f64>,
     pub center_longitude: Option<f64>,
     pub layers: Option<Vec<GeofenceLayer>>,
+    /// `Some(None)` clears the altitude ceiling; `Some(Some(ft))` sets it;
+    /// `None` leaves it unchanged.
+    #[serde(default, deserialize_with = "deserialize_present_field")]
+    pub max_altitude_msl_ft: Option<Option<i32>>,
+    pub ignored_categories: Option<Vec<i16>>,
+}
+
+/// Map a field that is present in the JSON (including an explicit `null`) to
+/// `Some(_)`, so an absent field (`None` via `#[serde(default)]`) can be told
+/// apart from `null` (`Some(None)`).
+fn deserialize_present_field<'de, D>(deserializer: D) -> Result<Option<Option<i32>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Option::<i32>::deserialize(deserializer).map(Some)
 }
 
 impl UpdateGeofenceRequest {
@@ -178,28 +233,61 @@ impl UpdateGeofenceRequest {
     }
 }
 
-/// Geofence subscriber entry
+/// A delivery channel a subscriber can receive geofence exit notifications on.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, DbEnum, Serialize, Deserialize, TS)]
+#[db_enum(existing_type_path = "crate::schema::sql_types::NotificationChannel")]
+#[ts(export, export_to = "../web/src/lib/types/generated/")]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationChannel {
+    #[db_enum(rename = "email")]
+    Email,
+    #[db_enum(rename = "webhook")]
+    Webhook,
+    #[db_enum(rename = "sms")]
+    Sms,
+    #[db_enum(rename = "push")]
+    Push,
+}
+
+/// One channel a user is subscribed to for a geofence, with whatever routing
+/// metadata that channel needs (a webhook URL, a phone number, ...).
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[ts(export, export_to = "../web/src/lib/types/generated/")]
 #[serde(rename_all = "camelCase")]
 pub struct GeofenceSubscriber {
     pub geofence_id: Uuid,
     pub user_id: Uuid,
-    pub send_email: bool,
+    pub channel: NotificationChannel,
+    /// Channel-specific routing metadata, e.g. `{"url": "..."}` for webhook
+    /// or `{"phoneNumber": "..."}` for sms. `None` for channels (like email)
+    /// that are routed entirely from the user's account.
+    pub channel_config: Option<serde_json::Value>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
-/// Request to subscribe to a geofence
+/// One channel to subscribe to, with its routing metadata.
+#[derive(Debug, Clone, Deserialize, TS)]
+#[ts(export, export_to = "../web/src/lib/types/generated/")]
+#[serde(rename_all = "camelCase")]
+pub struct SubscriberChannel {
+    pub channel: NotificationChannel,
+    pub channel_config: Option<serde_json::Value>,
+}
+
+/// Request to subscribe to a geofence on one or more channels
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SubscribeToGeofenceRequest {
-    #[serde(default = "default_true")]
-    pub send_email: bool,
+    #[serde(default = "default_email_channel")]
+    pub channels: Vec<SubscriberChannel>,
 }
 
-fn default_true() -> bool {
-    true
+fn default_email_channel() -> Vec<SubscriberChannel> {
+    vec![SubscriberChannel {
+        channel: NotificationChannel::Email,
+        channel_config: None,
+    }]
 }
 
 /// Aircraft-geofence link
@@ -219,6 +307,37 @@ pub struct LinkAircraftRequest {
     pub aircraft_id: Uuid,
 }
 
+/// One aircraft-geofence pairing that was skipped by a batch link operation
+/// because it already existed.
+#[derive(Debug, Clone, Copy, Serialize, TS)]
+#[ts(export, export_to = "../web/src/lib/types/generated/")]
+#[serde(rename_all = "camelCase")]
+pub struct SkippedAircraftLink {
+    pub geofence_id: Uuid,
+    pub aircraft_id: Uuid,
+}
+
+/// Outcome of a batch aircraft-link insert: which links were newly created
+/// versus skipped because they already existed, so a caller importing a
+/// whole fleet can tell the two apart instead of getting back one count.
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../web/src/lib/types/generated/")]
+#[serde(rename_all = "camelCase")]
+pub struct BatchLinkResult {
+    pub inserted: Vec<AircraftGeofence>,
+    pub skipped: Vec<SkippedAircraftLink>,
+}
+
+/// Number of deliveries attempted on one channel for an exit event, e.g.
+/// `{channel: Email, count: 3}` for 3 subscriber emails sent.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../web/src/lib/types/generated/")]
+#[serde(rename_all = "camelCase")]
+pub struct ChannelDeliveryCount {
+    pub channel: NotificationChannel,
+    pub count: i32,
+}
+
 /// Geofence exit event - recorded when an aircraft exits a geofence boundary
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[ts(export, export_to = "../web/src/lib/types/generated/")]
@@ -232,11 +351,120 @@ pub struct GeofenceExitEvent {
     pub exit_latitude: f64,
     pub exit_longitude: f64,
     pub exit_altitude_msl_ft: Option<i32>,
+    /// The aircraft's ADS-B emitter category code (see
+    /// [`AdsbEmitterCategory::numeric_code`]) at the time of exit, if known.
+    pub exit_aircraft_category: Option<i16>,
     pub exit_layer: GeofenceLayer,
-    pub email_notifications_sent: i32,
+    /// Count of deliveries attempted per [`NotificationChannel`], one entry
+    /// per channel that has had at least one attempt.
+    pub delivery_counts: Vec<ChannelDeliveryCount>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Geofence entry event - recorded when an aircraft enters a geofence
+/// boundary. Paired with a later [`GeofenceExitEvent`] for the same
+/// `(geofence_id, flight_id)` to compute dwell time - see
+/// `GeofenceRepository::get_dwell_intervals_for_flight`.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../web/src/lib/types/generated/")]
+#[serde(rename_all = "camelCase")]
+pub struct GeofenceEntryEvent {
+    pub id: Uuid,
+    pub geofence_id: Uuid,
+    pub flight_id: Uuid,
+    pub aircraft_id: Uuid,
+    pub entry_time: DateTime<Utc>,
+    pub entry_latitude: f64,
+    pub entry_longitude: f64,
+    pub entry_altitude_msl_ft: Option<i32>,
+    pub entry_layer: GeofenceLayer,
     pub created_at: DateTime<Utc>,
 }
 
+/// One entry/exit pairing for a flight inside a geofence, used to answer
+/// "how long did this aircraft loiter in my area". `exit_time` and
+/// `dwell_seconds` are `None` when the entry hasn't been paired with a
+/// recorded exit yet (the aircraft is still inside, or the flight ended
+/// without an exit ever being detected).
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../web/src/lib/types/generated/")]
+#[serde(rename_all = "camelCase")]
+pub struct DwellInterval {
+    pub geofence_id: Uuid,
+    pub entry_time: DateTime<Utc>,
+    pub exit_time: Option<DateTime<Utc>>,
+    pub dwell_seconds: Option<i64>,
+}
+
+/// Time granularity for bucketing exit-event counts in an analytics series.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../web/src/lib/types/generated/")]
+#[serde(rename_all = "snake_case")]
+pub enum TimeBucket {
+    Hour,
+    Day,
+    Week,
+}
+
+impl TimeBucket {
+    /// The `date_trunc` field name for this bucket.
+    pub fn trunc_field(self) -> &'static str {
+        match self {
+            TimeBucket::Hour => "hour",
+            TimeBucket::Day => "day",
+            TimeBucket::Week => "week",
+        }
+    }
+}
+
+/// Opaque keyset cursor for paginating exit events by `(exit_time, id)`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../web/src/lib/types/generated/")]
+pub struct ExitEventCursor {
+    pub exit_time: DateTime<Utc>,
+    pub id: Uuid,
+}
+
+/// Filter for `GeofenceRepository::query_exit_events`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExitEventFilter {
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub geofence_id: Option<Uuid>,
+    pub aircraft_id: Option<Uuid>,
+    pub flight_id: Option<Uuid>,
+    pub bucket: TimeBucket,
+    /// Only return events strictly after this cursor; `None` starts from the
+    /// beginning of the range.
+    pub after: Option<ExitEventCursor>,
+    pub page_size: i64,
+}
+
+/// One point in the bucketed exit-event count series, used to drive a
+/// breach-history dashboard.
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../web/src/lib/types/generated/")]
+#[serde(rename_all = "camelCase")]
+pub struct ExitEventSeriesPoint {
+    pub bucket_start: DateTime<Utc>,
+    pub geofence_id: Uuid,
+    pub count: i64,
+}
+
+/// Result of `GeofenceRepository::query_exit_events`: a keyset-paginated page
+/// of raw events plus the aggregate series for the whole filtered range (not
+/// just the current page).
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../web/src/lib/types/generated/")]
+#[serde(rename_all = "camelCase")]
+pub struct ExitEventQueryResult {
+    pub events: Vec<GeofenceExitEvent>,
+    pub series: Vec<ExitEventSeriesPoint>,
+    /// Cursor to pass as `after` to fetch the next page; `None` at the end.
+    pub next_cursor: Option<ExitEventCursor>,
+}
+
 /// Geofence with linked aircraft count and subscriber count
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[ts(export, export_to = "../web/src/lib/types/generated/")]
@@ -273,3 +501,56 @@ pub struct GeofenceDetailResponse {
 pub struct GeofenceExitEventsResponse {
     pub events: Vec<GeofenceExitEvent>,
 }
+
+/// Entry events response
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../web/src/lib/types/generated/")]
+#[serde(rename_all = "camelCase")]
+pub struct GeofenceEntryEventsResponse {
+    pub events: Vec<GeofenceEntryEvent>,
+}
+
+/// Dwell intervals response
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../web/src/lib/types/generated/")]
+#[serde(rename_all = "camelCase")]
+pub struct DwellIntervalsResponse {
+    pub intervals: Vec<DwellInterval>,
+}
+
+/// Status of a queued geofence notification job.
+///
+/// Jobs start `New` and are atomically claimed into `Running` by a worker
+/// (see `GeofenceRepository::claim_next_job`). There is no terminal "done"
+/// state: completed jobs are deleted, failed-but-retryable jobs go back to
+/// `New`, and permanently-failed jobs are deleted after exhausting retries.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, DbEnum, Serialize, Deserialize)]
+#[db_enum(existing_type_path = "crate::schema::sql_types::JobStatus")]
+pub enum JobStatus {
+    New,
+    Running,
+}
+
+/// A durable, at-least-once notification job for a geofence exit event.
+///
+/// `heartbeat` does double duty: while `status` is `Running` it's the last
+/// time a worker reported liveness (used by `reap_stale_jobs` to recover from
+/// crashed workers); while `status` is `New` it's the earliest time the job
+/// may be claimed again (used by `fail_job` for backoff). `NULL` means
+/// "claimable immediately".
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeofenceNotificationJob {
+    pub id: Uuid,
+    pub exit_event_id: Uuid,
+    pub subscriber_user_id: Uuid,
+    /// Which channel this job delivers on - determines whether a worker
+    /// sends an email, POSTs a webhook, or pushes a notification. `job`
+    /// carries whatever payload that channel needs (e.g. the webhook URL
+    /// and body, pre-rendered).
+    pub channel: NotificationChannel,
+    pub job: serde_json::Value,
+    pub status: JobStatus,
+    pub attempts: i32,
+    pub heartbeat: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
@@ -12,14 +12,91 @@ use std::time::Duration;
 use uuid::Uuid;
 
 use crate::geofence::{
-    AircraftGeofence, CreateGeofenceRequest, Geofence, GeofenceExitEvent, GeofenceLayer,
-    GeofenceSubscriber, UpdateGeofenceRequest,
+    AircraftGeofence, BatchLinkResult, ChannelDeliveryCount, CreateGeofenceRequest, DwellInterval,
+    ExitEventCursor, ExitEventFilter, ExitEventQueryResult, ExitEventSeriesPoint, Geofence,
+    GeofenceEntryEvent, GeofenceExitEvent, GeofenceLayer, GeofenceNotificationJob,
+    GeofenceSubscriber, JobStatus, NotificationChannel, SkippedAircraftLink, SubscriberChannel,
+    UpdateGeofenceRequest,
 };
 use crate::postgis_functions::{st_make_point, st_set_srid, st_x, st_y};
-use crate::schema::{aircraft_geofences, geofence_exit_events, geofence_subscribers, geofences};
+use crate::schema::{
+    aircraft_geofences, geofence_entry_events, geofence_exit_events, geofence_notification_jobs,
+    geofence_subscribers, geofences,
+};
 
 type PgPool = Pool<ConnectionManager<PgConnection>>;
 
+/// Postgres NOTIFY channel used to propagate geofence-link cache invalidation
+/// across horizontally scaled `soar` instances. Payload is the affected
+/// aircraft ID as text.
+const GEOFENCE_CHANGED_CHANNEL: &str = "geofence_changed";
+
+/// Issue `NOTIFY geofence_changed, '<aircraft_id>'` on the given connection.
+/// Intended to be called inside the same transaction as the mutation so the
+/// notification only fires if the mutation commits.
+fn notify_geofence_changed(conn: &mut PgConnection, aircraft_id: Uuid) -> QueryResult<()> {
+    diesel::sql_query("SELECT pg_notify($1, $2)")
+        .bind::<sql_types::Text, _>(GEOFENCE_CHANGED_CHANNEL)
+        .bind::<sql_types::Text, _>(aircraft_id.to_string())
+        .execute(conn)?;
+    Ok(())
+}
+
+/// Notify every aircraft currently linked to `geofence_id` and return their
+/// IDs, so a geofence-level mutation (update/delete) invalidates every
+/// affected aircraft's cache entry rather than just one.
+fn notify_linked_aircraft(conn: &mut PgConnection, geofence_id: Uuid) -> QueryResult<Vec<Uuid>> {
+    use aircraft_geofences::dsl;
+
+    let aircraft_ids: Vec<Uuid> = dsl::aircraft_geofences
+        .filter(dsl::geofence_id.eq(geofence_id))
+        .select(dsl::aircraft_id)
+        .load(conn)?;
+
+    for aircraft_id in &aircraft_ids {
+        notify_geofence_changed(conn, *aircraft_id)?;
+    }
+
+    Ok(aircraft_ids)
+}
+
+/// Open a dedicated `tokio_postgres` connection, `LISTEN` on
+/// `geofence_changed`, and invalidate `cache` for each notified aircraft ID
+/// until the connection is lost. Returns on disconnect so the caller can
+/// reconnect with backoff.
+async fn listen_for_geofence_changes(cache: &Cache<Uuid, Vec<Geofence>>) -> Result<()> {
+    use futures_util::{StreamExt, stream};
+    use tokio_postgres::{AsyncMessage, NoTls};
+
+    let database_url = std::env::var("DATABASE_URL")
+        .map_err(|_| anyhow::anyhow!("DATABASE_URL environment variable not set"))?;
+
+    let (client, mut connection) = tokio_postgres::connect(&database_url, NoTls).await?;
+
+    client
+        .batch_execute(&format!("LISTEN {GEOFENCE_CHANGED_CHANNEL}"))
+        .await?;
+
+    let mut messages = stream::poll_fn(move |cx| connection.poll_message(cx));
+
+    while let Some(message) = messages.next().await {
+        match message {
+            Ok(AsyncMessage::Notification(notification)) => {
+                if let Ok(aircraft_id) = notification.payload().parse::<Uuid>() {
+                    cache.invalidate(&aircraft_id);
+                    metrics::counter!("geofence_repo.cache_invalidation.remote").increment(1);
+                }
+            }
+            Ok(_) => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    Err(anyhow::anyhow!(
+        "geofence_changed listen connection closed unexpectedly"
+    ))
+}
+
 // Database record types
 
 #[derive(Queryable, Selectable, Insertable, Debug, Clone)]
@@ -28,7 +105,8 @@ type PgPool = Pool<ConnectionManager<PgConnection>>;
 pub struct GeofenceSubscriberRecord {
     pub geofence_id: Uuid,
     pub user_id: Uuid,
-    pub send_email: bool,
+    pub channel: NotificationChannel,
+    pub channel_config: Option<JsonValue>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -38,7 +116,8 @@ impl From<GeofenceSubscriberRecord> for GeofenceSubscriber {
         GeofenceSubscriber {
             geofence_id: record.geofence_id,
             user_id: record.user_id,
-            send_email: record.send_email,
+            channel: record.channel,
+            channel_config: record.channel_config,
             created_at: record.created_at,
             updated_at: record.updated_at,
         }
@@ -76,13 +155,63 @@ pub struct GeofenceExitEventRecord {
     pub exit_latitude: f64,
     pub exit_longitude: f64,
     pub exit_altitude_msl_ft: Option<i32>,
+    pub exit_aircraft_category: Option<i16>,
     pub exit_layer_floor_ft: i32,
     pub exit_layer_ceiling_ft: i32,
     pub exit_layer_radius_nm: f64,
-    pub email_notifications_sent: i32,
+    pub delivery_counts: JsonValue,
     pub created_at: DateTime<Utc>,
 }
 
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = geofence_entry_events)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct GeofenceEntryEventRecord {
+    pub id: Uuid,
+    pub geofence_id: Uuid,
+    pub flight_id: Uuid,
+    pub aircraft_id: Uuid,
+    pub entry_time: DateTime<Utc>,
+    pub entry_latitude: f64,
+    pub entry_longitude: f64,
+    pub entry_altitude_msl_ft: Option<i32>,
+    pub entry_layer_floor_ft: i32,
+    pub entry_layer_ceiling_ft: i32,
+    pub entry_layer_radius_nm: f64,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = geofence_notification_jobs)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct GeofenceNotificationJobRecord {
+    pub id: Uuid,
+    pub exit_event_id: Uuid,
+    pub subscriber_user_id: Uuid,
+    pub channel: NotificationChannel,
+    pub job: JsonValue,
+    pub status: JobStatus,
+    pub attempts: i32,
+    pub heartbeat: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<GeofenceNotificationJobRecord> for GeofenceNotificationJob {
+    fn from(record: GeofenceNotificationJobRecord) -> Self {
+        GeofenceNotificationJob {
+            id: record.id,
+            exit_event_id: record.exit_event_id,
+            subscriber_user_id: record.subscriber_user_id,
+            channel: record.channel,
+            job: record.job,
+            status: record.status,
+            attempts: record.attempts,
+            heartbeat: record.heartbeat,
+            created_at: record.created_at,
+        }
+    }
+}
+
 /// Type alias for the select expression that extracts geofence data with coordinates
 type GeofenceSelectExpr = (
     geofences::id,
@@ -92,6 +221,8 @@ type GeofenceSelectExpr = (
     st_x<geofences::center>,
     geofences::max_radius_meters,
     geofences::layers,
+    geofences::max_altitude_msl_ft,
+    geofences::ignored_categories,
     geofences::owner_user_id,
     geofences::club_id,
     geofences::created_at,
@@ -107,6 +238,8 @@ type GeofenceRow = (
     f64,
     f64,
     JsonValue,
+    Option<i32>,
+    JsonValue,
     Uuid,
     Option<Uuid>,
     DateTime<Utc>,
@@ -124,6 +257,8 @@ fn geofence_select() -> GeofenceSelectExpr {
         st_x(dsl::center),
         dsl::max_radius_meters,
         dsl::layers,
+        dsl::max_altitude_msl_ft,
+        dsl::ignored_categories,
         dsl::owner_user_id,
         dsl::club_id,
         dsl::created_at,
@@ -134,6 +269,7 @@ fn geofence_select() -> GeofenceSelectExpr {
 /// Convert a row to a Geofence
 fn row_to_geofence(row: GeofenceRow) -> Result<Geofence> {
     let layers: Vec<GeofenceLayer> = serde_json::from_value(row.6)?;
+    let ignored_categories: Vec<i16> = serde_json::from_value(row.8)?;
     Ok(Geofence {
         id: row.0,
         name: row.1,
@@ -142,10 +278,12 @@ fn row_to_geofence(row: GeofenceRow) -> Result<Geofence> {
         center_longitude: row.4,
         max_radius_meters: row.5,
         layers,
-        owner_user_id: row.7,
-        club_id: row.8,
-        created_at: row.9,
-        updated_at: row.10,
+        max_altitude_msl_ft: row.7,
+        ignored_categories,
+        owner_user_id: row.9,
+        club_id: row.10,
+        created_at: row.11,
+        updated_at: row.12,
     })
 }
 
@@ -172,6 +310,31 @@ impl GeofenceRepository {
         }
     }
 
+    /// Spawn a background task that `LISTEN`s on the `geofence_changed`
+    /// Postgres channel (see `notify_geofence_changed`) and invalidates the
+    /// matching `geofence_cache` entry on every notification. This keeps the
+    /// 60-second TTL cache coherent across horizontally scaled `soar`
+    /// instances instead of only within the process that made the mutation.
+    /// Reconnects with a fixed delay if the listen connection drops.
+    ///
+    /// Callers should invoke this once per long-lived `GeofenceRepository`
+    /// (e.g. the one held by the fix-processing pipeline), not per request.
+    pub fn spawn_cache_invalidation_listener(&self) -> tokio::task::JoinHandle<()> {
+        let cache = self.geofence_cache.clone();
+
+        tokio::spawn(async move {
+            loop {
+                if let Err(e) = listen_for_geofence_changes(&cache).await {
+                    tracing::error!(
+                        error = %e,
+                        "geofence cache invalidation listener disconnected, retrying in 5s"
+                    );
+                }
+                tokio::time::sleep(Duration::from_secs(5)).await;
+            }
+        })
+    }
+
     /// Create a new geofence
     pub async fn create(
         &self,
@@ -182,6 +345,7 @@ impl GeofenceRepository {
 
         let pool = self.pool.clone();
         let layers_json = serde_json::to_value(&request.layers)?;
+        let ignored_categories_json = serde_json::to_value(&request.ignored_categories)?;
         let max_radius = request.max_radius_meters();
         let lat = request.center_latitude;
         let lon = request.center_longitude;
@@ -197,6 +361,8 @@ impl GeofenceRepository {
                     dsl::center.eq(st_set_srid(st_make_point(lon, lat), 4326)),
                     dsl::max_radius_meters.eq(max_radius),
                     dsl::layers.eq(&layers_json),
+                    dsl::max_altitude_msl_ft.eq(request.max_altitude_msl_ft),
+                    dsl::ignored_categories.eq(&ignored_categories_json),
                     dsl::owner_user_id.eq(owner_user_id),
                     dsl::club_id.eq(request.club_id),
                 ))
@@ -214,6 +380,66 @@ impl GeofenceRepository {
         .await?
     }
 
+    /// Create many geofences atomically in a single transaction, for clubs
+    /// bulk-importing boundaries. All rows commit together; an error in any
+    /// one request rolls the whole batch back rather than leaving a partial
+    /// set created.
+    pub async fn create_batch(
+        &self,
+        owner_user_id: Uuid,
+        requests: Vec<CreateGeofenceRequest>,
+    ) -> Result<Vec<Geofence>> {
+        use geofences::dsl;
+
+        if requests.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Vec<Geofence>> {
+            let mut conn = pool.get()?;
+
+            conn.transaction::<_, anyhow::Error, _>(|conn| {
+                let mut created = Vec::with_capacity(requests.len());
+
+                for request in requests {
+                    let layers_json = serde_json::to_value(&request.layers)?;
+                    let ignored_categories_json =
+                        serde_json::to_value(&request.ignored_categories)?;
+                    let max_radius = request.max_radius_meters();
+                    let lat = request.center_latitude;
+                    let lon = request.center_longitude;
+
+                    let id: Uuid = diesel::insert_into(dsl::geofences)
+                        .values((
+                            dsl::name.eq(&request.name),
+                            dsl::description.eq(&request.description),
+                            dsl::center.eq(st_set_srid(st_make_point(lon, lat), 4326)),
+                            dsl::max_radius_meters.eq(max_radius),
+                            dsl::layers.eq(&layers_json),
+                            dsl::max_altitude_msl_ft.eq(request.max_altitude_msl_ft),
+                            dsl::ignored_categories.eq(&ignored_categories_json),
+                            dsl::owner_user_id.eq(owner_user_id),
+                            dsl::club_id.eq(request.club_id),
+                        ))
+                        .returning(dsl::id)
+                        .get_result(conn)?;
+
+                    let row: GeofenceRow = dsl::geofences
+                        .filter(dsl::id.eq(id))
+                        .select(geofence_select())
+                        .first(conn)?;
+
+                    created.push(row_to_geofence(row)?);
+                }
+
+                Ok(created)
+            })
+        })
+        .await?
+    }
+
     /// Get a geofence by ID
     pub async fn get_by_id(&self, id: Uuid) -> Result<Option<Geofence>> {
         use geofences::dsl;
@@ -266,6 +492,10 @@ impl GeofenceRepository {
                 max_radius_meters: f64,
                 #[diesel(sql_type = sql_types::Jsonb)]
                 layers: JsonValue,
+                #[diesel(sql_type = sql_types::Nullable<sql_types::Integer>)]
+                max_altitude_msl_ft: Option<i32>,
+                #[diesel(sql_type = sql_types::Jsonb)]
+                ignored_categories: JsonValue,
                 #[diesel(sql_type = sql_types::Uuid)]
                 owner_user_id: Uuid,
                 #[diesel(sql_type = sql_types::Nullable<sql_types::Uuid>)]
@@ -285,7 +515,9 @@ impl GeofenceRepository {
                 SELECT g.id, g.name, g.description,
                        ST_Y(g.center) as center_latitude,
                        ST_X(g.center) as center_longitude,
-                       g.max_radius_meters, g.layers, g.owner_user_id, g.club_id,
+                       g.max_radius_meters, g.layers,
+                       g.max_altitude_msl_ft, g.ignored_categories,
+                       g.owner_user_id, g.club_id,
                        g.created_at, g.updated_at,
                        COALESCE(ac.aircraft_count, 0) as aircraft_count,
                        COALESCE(sc.subscriber_count, 0) as subscriber_count
@@ -313,6 +545,8 @@ impl GeofenceRepository {
                 .into_iter()
                 .map(|r| {
                     let layers: Vec<GeofenceLayer> = serde_json::from_value(r.layers)?;
+                    let ignored_categories: Vec<i16> =
+                        serde_json::from_value(r.ignored_categories)?;
                     Ok((
                         Geofence {
                             id: r.id,
@@ -322,6 +556,8 @@ impl GeofenceRepository {
                             center_longitude: r.center_longitude,
                             max_radius_meters: r.max_radius_meters,
                             layers,
+                            max_altitude_msl_ft: r.max_altitude_msl_ft,
+                            ignored_categories,
                             owner_user_id: r.owner_user_id,
                             club_id: r.club_id,
                             created_at: r.created_at,
@@ -361,6 +597,10 @@ impl GeofenceRepository {
                 max_radius_meters: f64,
                 #[diesel(sql_type = sql_types::Jsonb)]
                 layers: JsonValue,
+                #[diesel(sql_type = sql_types::Nullable<sql_types::Integer>)]
+                max_altitude_msl_ft: Option<i32>,
+                #[diesel(sql_type = sql_types::Jsonb)]
+                ignored_categories: JsonValue,
                 #[diesel(sql_type = sql_types::Uuid)]
                 owner_user_id: Uuid,
                 #[diesel(sql_type = sql_types::Nullable<sql_types::Uuid>)]
@@ -380,7 +620,9 @@ impl GeofenceRepository {
                 SELECT g.id, g.name, g.description,
                        ST_Y(g.center) as center_latitude,
                        ST_X(g.center) as center_longitude,
-                       g.max_radius_meters, g.layers, g.owner_user_id, g.club_id,
+                       g.max_radius_meters, g.layers,
+                       g.max_altitude_msl_ft, g.ignored_categories,
+                       g.owner_user_id, g.club_id,
                        g.created_at, g.updated_at,
                        COALESCE(ac.aircraft_count, 0) as aircraft_count,
                        COALESCE(sc.subscriber_count, 0) as subscriber_count
@@ -406,6 +648,8 @@ impl GeofenceRepository {
                 .into_iter()
                 .map(|r| {
                     let layers: Vec<GeofenceLayer> = serde_json::from_value(r.layers)?;
+                    let ignored_categories: Vec<i16> =
+                        serde_json::from_value(r.ignored_categories)?;
                     Ok((
                         Geofence {
                             id: r.id,
@@ -415,6 +659,8 @@ impl GeofenceRepository {
                             center_longitude: r.center_longitude,
                             max_radius_meters: r.max_radius_meters,
                             layers,
+                            max_altitude_msl_ft: r.max_altitude_msl_ft,
+                            ignored_categories,
                             owner_user_id: r.owner_user_id,
                             club_id: r.club_id,
                             created_at: r.created_at,
@@ -439,67 +685,93 @@ impl GeofenceRepository {
 
         let pool = self.pool.clone();
 
-        tokio::task::spawn_blocking(move || -> Result<Option<Geofence>> {
-            let mut conn = pool.get()?;
-
-            // Check if geofence exists
-            let exists: bool = diesel::select(diesel::dsl::exists(
-                dsl::geofences
-                    .filter(dsl::id.eq(id))
-                    .filter(dsl::deleted_at.is_null()),
-            ))
-            .get_result(&mut conn)?;
-
-            if !exists {
-                return Ok(None);
-            }
-
-            // Build dynamic update
-            // Note: Diesel's AsChangeset doesn't work well with optional PostGIS expressions,
-            // so we update fields individually when present
-            if let Some(ref name) = request.name {
-                diesel::update(dsl::geofences.filter(dsl::id.eq(id)))
-                    .set(dsl::name.eq(name))
-                    .execute(&mut conn)?;
-            }
-
-            if request.description.is_some() {
-                diesel::update(dsl::geofences.filter(dsl::id.eq(id)))
-                    .set(dsl::description.eq(&request.description))
-                    .execute(&mut conn)?;
-            }
-
-            if let (Some(lon), Some(lat)) = (request.center_longitude, request.center_latitude) {
-                diesel::update(dsl::geofences.filter(dsl::id.eq(id)))
-                    .set(dsl::center.eq(st_set_srid(st_make_point(lon, lat), 4326)))
-                    .execute(&mut conn)?;
-            }
+        let (result, linked_aircraft) =
+            tokio::task::spawn_blocking(move || -> Result<(Option<Geofence>, Vec<Uuid>)> {
+                let mut conn = pool.get()?;
 
-            if let Some(ref layers) = request.layers {
-                let layers_json = serde_json::to_value(layers)?;
-                let max_radius = request.max_radius_meters();
-                diesel::update(dsl::geofences.filter(dsl::id.eq(id)))
-                    .set((
-                        dsl::layers.eq(&layers_json),
-                        dsl::max_radius_meters.eq(max_radius.unwrap_or(0.0)),
+                conn.transaction::<_, anyhow::Error, _>(|conn| {
+                    // Check if geofence exists
+                    let exists: bool = diesel::select(diesel::dsl::exists(
+                        dsl::geofences
+                            .filter(dsl::id.eq(id))
+                            .filter(dsl::deleted_at.is_null()),
                     ))
-                    .execute(&mut conn)?;
-            }
-
-            // Update timestamp
-            diesel::update(dsl::geofences.filter(dsl::id.eq(id)))
-                .set(dsl::updated_at.eq(Utc::now()))
-                .execute(&mut conn)?;
+                    .get_result(conn)?;
+
+                    if !exists {
+                        return Ok((None, Vec::new()));
+                    }
+
+                    // Build dynamic update
+                    // Note: Diesel's AsChangeset doesn't work well with optional PostGIS expressions,
+                    // so we update fields individually when present
+                    if let Some(ref name) = request.name {
+                        diesel::update(dsl::geofences.filter(dsl::id.eq(id)))
+                            .set(dsl::name.eq(name))
+                            .execute(conn)?;
+                    }
+
+                    if request.description.is_some() {
+                        diesel::update(dsl::geofences.filter(dsl::id.eq(id)))
+                            .set(dsl::description.eq(&request.description))
+                            .execute(conn)?;
+                    }
+
+                    if let (Some(lon), Some(lat)) =
+                        (request.center_longitude, request.center_latitude)
+                    {
+                        diesel::update(dsl::geofences.filter(dsl::id.eq(id)))
+                            .set(dsl::center.eq(st_set_srid(st_make_point(lon, lat), 4326)))
+                            .execute(conn)?;
+                    }
+
+                    if let Some(ref layers) = request.layers {
+                        let layers_json = serde_json::to_value(layers)?;
+                        let max_radius = request.max_radius_meters();
+                        diesel::update(dsl::geofences.filter(dsl::id.eq(id)))
+                            .set((
+                                dsl::layers.eq(&layers_json),
+                                dsl::max_radius_meters.eq(max_radius.unwrap_or(0.0)),
+                            ))
+                            .execute(conn)?;
+                    }
+
+                    if let Some(max_altitude_msl_ft) = request.max_altitude_msl_ft {
+                        diesel::update(dsl::geofences.filter(dsl::id.eq(id)))
+                            .set(dsl::max_altitude_msl_ft.eq(max_altitude_msl_ft))
+                            .execute(conn)?;
+                    }
+
+                    if let Some(ref ignored_categories) = request.ignored_categories {
+                        let ignored_categories_json = serde_json::to_value(ignored_categories)?;
+                        diesel::update(dsl::geofences.filter(dsl::id.eq(id)))
+                            .set(dsl::ignored_categories.eq(&ignored_categories_json))
+                            .execute(conn)?;
+                    }
+
+                    // Update timestamp
+                    diesel::update(dsl::geofences.filter(dsl::id.eq(id)))
+                        .set(dsl::updated_at.eq(Utc::now()))
+                        .execute(conn)?;
+
+                    // Fetch updated record
+                    let row: GeofenceRow = dsl::geofences
+                        .filter(dsl::id.eq(id))
+                        .select(geofence_select())
+                        .first(conn)?;
+
+                    let linked_aircraft = notify_linked_aircraft(conn, id)?;
+
+                    Ok((Some(row_to_geofence(row)?), linked_aircraft))
+                })
+            })
+            .await??;
 
-            // Fetch updated record
-            let row: GeofenceRow = dsl::geofences
-                .filter(dsl::id.eq(id))
-                .select(geofence_select())
-                .first(&mut conn)?;
+        for aircraft_id in linked_aircraft {
+            self.geofence_cache.invalidate(&aircraft_id);
+        }
 
-            Ok(Some(row_to_geofence(row)?))
-        })
-        .await?
+        Ok(result)
     }
 
     /// Soft delete a geofence
@@ -508,16 +780,27 @@ impl GeofenceRepository {
 
         let pool = self.pool.clone();
 
-        tokio::task::spawn_blocking(move || -> Result<bool> {
-            let mut conn = pool.get()?;
+        let (deleted, linked_aircraft) =
+            tokio::task::spawn_blocking(move || -> Result<(bool, Vec<Uuid>)> {
+                let mut conn = pool.get()?;
 
-            let rows_affected = diesel::update(dsl::geofences.filter(dsl::id.eq(id)))
-                .set(dsl::deleted_at.eq(Some(Utc::now())))
-                .execute(&mut conn)?;
+                conn.transaction::<_, anyhow::Error, _>(|conn| {
+                    let rows_affected = diesel::update(dsl::geofences.filter(dsl::id.eq(id)))
+                        .set(dsl::deleted_at.eq(Some(Utc::now())))
+                        .execute(conn)?;
 
-            Ok(rows_affected > 0)
-        })
-        .await?
+                    let linked_aircraft = notify_linked_aircraft(conn, id)?;
+
+                    Ok((rows_affected > 0, linked_aircraft))
+                })
+            })
+            .await??;
+
+        for aircraft_id in &linked_aircraft {
+            self.geofence_cache.invalidate(aircraft_id);
+        }
+
+        Ok(deleted)
     }
 
     // ==================== Aircraft Links ====================
@@ -531,17 +814,173 @@ impl GeofenceRepository {
         tokio::task::spawn_blocking(move || -> Result<()> {
             let mut conn = pool.get()?;
 
-            diesel::insert_into(dsl::aircraft_geofences)
-                .values((
-                    dsl::geofence_id.eq(geofence_id),
-                    dsl::aircraft_id.eq(aircraft_id),
-                ))
-                .on_conflict_do_nothing()
-                .execute(&mut conn)?;
+            conn.transaction::<_, anyhow::Error, _>(|conn| {
+                diesel::insert_into(dsl::aircraft_geofences)
+                    .values((
+                        dsl::geofence_id.eq(geofence_id),
+                        dsl::aircraft_id.eq(aircraft_id),
+                    ))
+                    .on_conflict_do_nothing()
+                    .execute(conn)?;
 
-            Ok(())
+                notify_geofence_changed(conn, aircraft_id)?;
+                Ok(())
+            })
+        })
+        .await??;
+
+        self.geofence_cache.invalidate(&aircraft_id);
+        Ok(())
+    }
+
+    /// Link many aircraft to one geofence in a single multi-row insert,
+    /// for clubs importing their whole fleet. Reports which links were
+    /// newly inserted versus skipped because they already existed, rather
+    /// than collapsing the result into one count.
+    pub async fn add_aircraft_batch(
+        &self,
+        geofence_id: Uuid,
+        aircraft_ids: &[Uuid],
+    ) -> Result<BatchLinkResult> {
+        use aircraft_geofences::dsl;
+
+        if aircraft_ids.is_empty() {
+            return Ok(BatchLinkResult {
+                inserted: Vec::new(),
+                skipped: Vec::new(),
+            });
+        }
+
+        let pool = self.pool.clone();
+        let aircraft_ids = aircraft_ids.to_vec();
+
+        let (inserted, aircraft_ids): (Vec<AircraftGeofenceRecord>, Vec<Uuid>) =
+            tokio::task::spawn_blocking(
+                move || -> Result<(Vec<AircraftGeofenceRecord>, Vec<Uuid>)> {
+                    let mut conn = pool.get()?;
+
+                    conn.transaction::<_, anyhow::Error, _>(|conn| {
+                        let values: Vec<_> = aircraft_ids
+                            .iter()
+                            .map(|&aircraft_id| {
+                                (
+                                    dsl::geofence_id.eq(geofence_id),
+                                    dsl::aircraft_id.eq(aircraft_id),
+                                )
+                            })
+                            .collect();
+
+                        let inserted: Vec<AircraftGeofenceRecord> =
+                            diesel::insert_into(dsl::aircraft_geofences)
+                                .values(values)
+                                .on_conflict_do_nothing()
+                                .returning(AircraftGeofenceRecord::as_returning())
+                                .get_results(conn)?;
+
+                        for &aircraft_id in &aircraft_ids {
+                            notify_geofence_changed(conn, aircraft_id)?;
+                        }
+
+                        Ok((inserted, aircraft_ids))
+                    })
+                },
+            )
+            .await??;
+
+        for aircraft_id in &aircraft_ids {
+            self.geofence_cache.invalidate(aircraft_id);
+        }
+
+        let inserted_ids: std::collections::HashSet<Uuid> =
+            inserted.iter().map(|r| r.aircraft_id).collect();
+        let skipped = aircraft_ids
+            .iter()
+            .filter(|id| !inserted_ids.contains(id))
+            .map(|&aircraft_id| SkippedAircraftLink {
+                geofence_id,
+                aircraft_id,
+            })
+            .collect();
+
+        Ok(BatchLinkResult {
+            inserted: inserted.into_iter().map(Into::into).collect(),
+            skipped,
+        })
+    }
+
+    /// Link many (geofence_id, aircraft_id) pairs in a single multi-row
+    /// insert, e.g. when importing a fleet across several fences at once.
+    pub async fn link_batch(&self, links: Vec<(Uuid, Uuid)>) -> Result<BatchLinkResult> {
+        use aircraft_geofences::dsl;
+
+        if links.is_empty() {
+            return Ok(BatchLinkResult {
+                inserted: Vec::new(),
+                skipped: Vec::new(),
+            });
+        }
+
+        let pool = self.pool.clone();
+        let links_for_task = links.clone();
+
+        let inserted: Vec<AircraftGeofenceRecord> =
+            tokio::task::spawn_blocking(move || -> Result<Vec<AircraftGeofenceRecord>> {
+                let mut conn = pool.get()?;
+
+                conn.transaction::<_, anyhow::Error, _>(|conn| {
+                    let values: Vec<_> = links_for_task
+                        .iter()
+                        .map(|&(geofence_id, aircraft_id)| {
+                            (
+                                dsl::geofence_id.eq(geofence_id),
+                                dsl::aircraft_id.eq(aircraft_id),
+                            )
+                        })
+                        .collect();
+
+                    let inserted: Vec<AircraftGeofenceRecord> =
+                        diesel::insert_into(dsl::aircraft_geofences)
+                            .values(values)
+                            .on_conflict_do_nothing()
+                            .returning(AircraftGeofenceRecord::as_returning())
+                            .get_results(conn)?;
+
+                    let distinct_aircraft: std::collections::HashSet<Uuid> = links_for_task
+                        .iter()
+                        .map(|&(_, aircraft_id)| aircraft_id)
+                        .collect();
+                    for aircraft_id in &distinct_aircraft {
+                        notify_geofence_changed(conn, *aircraft_id)?;
+                    }
+
+                    Ok(inserted)
+                })
+            })
+            .await??;
+
+        let distinct_aircraft: std::collections::HashSet<Uuid> =
+            links.iter().map(|&(_, aircraft_id)| aircraft_id).collect();
+        for aircraft_id in &distinct_aircraft {
+            self.geofence_cache.invalidate(aircraft_id);
+        }
+
+        let inserted_set: std::collections::HashSet<(Uuid, Uuid)> = inserted
+            .iter()
+            .map(|r| (r.geofence_id, r.aircraft_id))
+            .collect();
+        let skipped = links
+            .iter()
+            .filter(|pair| !inserted_set.contains(pair))
+            .map(|&(geofence_id, aircraft_id)| SkippedAircraftLink {
+                geofence_id,
+                aircraft_id,
+            })
+            .collect();
+
+        Ok(BatchLinkResult {
+            inserted: inserted.into_iter().map(Into::into).collect(),
+            skipped,
         })
-        .await?
     }
 
     /// Remove an aircraft from a geofence
@@ -550,19 +989,25 @@ impl GeofenceRepository {
 
         let pool = self.pool.clone();
 
-        tokio::task::spawn_blocking(move || -> Result<bool> {
+        let removed = tokio::task::spawn_blocking(move || -> Result<bool> {
             let mut conn = pool.get()?;
 
-            let rows_affected = diesel::delete(
-                dsl::aircraft_geofences
-                    .filter(dsl::geofence_id.eq(geofence_id))
-                    .filter(dsl::aircraft_id.eq(aircraft_id)),
-            )
-            .execute(&mut conn)?;
+            conn.transaction::<_, anyhow::Error, _>(|conn| {
+                let rows_affected = diesel::delete(
+                    dsl::aircraft_geofences
+                        .filter(dsl::geofence_id.eq(geofence_id))
+                        .filter(dsl::aircraft_id.eq(aircraft_id)),
+                )
+                .execute(conn)?;
 
-            Ok(rows_affected > 0)
+                notify_geofence_changed(conn, aircraft_id)?;
+                Ok(rows_affected > 0)
+            })
         })
-        .await?
+        .await??;
+
+        self.geofence_cache.invalidate(&aircraft_id);
+        Ok(removed)
     }
 
     /// Get aircraft IDs linked to a geofence
@@ -622,39 +1067,54 @@ impl GeofenceRepository {
 
     // ==================== Subscribers ====================
 
-    /// Subscribe a user to a geofence
+    /// Subscribe a user to a geofence on one or more channels. Keyed on
+    /// `(geofence_id, user_id, channel)`, so a user can register multiple
+    /// channels (e.g. email + webhook) for the same fence; re-subscribing to
+    /// a channel already held updates its `channel_config`.
     pub async fn add_subscriber(
         &self,
         geofence_id: Uuid,
         user_id: Uuid,
-        send_email: bool,
-    ) -> Result<GeofenceSubscriber> {
+        channels: Vec<SubscriberChannel>,
+    ) -> Result<Vec<GeofenceSubscriber>> {
         use geofence_subscribers::dsl;
 
         let pool = self.pool.clone();
 
-        tokio::task::spawn_blocking(move || -> Result<GeofenceSubscriber> {
+        tokio::task::spawn_blocking(move || -> Result<Vec<GeofenceSubscriber>> {
             let mut conn = pool.get()?;
 
-            let record: GeofenceSubscriberRecord = diesel::insert_into(dsl::geofence_subscribers)
-                .values((
-                    dsl::geofence_id.eq(geofence_id),
-                    dsl::user_id.eq(user_id),
-                    dsl::send_email.eq(send_email),
-                ))
-                .on_conflict((dsl::geofence_id, dsl::user_id))
-                .do_update()
-                .set(dsl::send_email.eq(send_email))
-                .returning(GeofenceSubscriberRecord::as_returning())
-                .get_result(&mut conn)?;
+            let mut subscribers = Vec::with_capacity(channels.len());
+            for channel in channels {
+                let record: GeofenceSubscriberRecord =
+                    diesel::insert_into(dsl::geofence_subscribers)
+                        .values((
+                            dsl::geofence_id.eq(geofence_id),
+                            dsl::user_id.eq(user_id),
+                            dsl::channel.eq(channel.channel),
+                            dsl::channel_config.eq(&channel.channel_config),
+                        ))
+                        .on_conflict((dsl::geofence_id, dsl::user_id, dsl::channel))
+                        .do_update()
+                        .set(dsl::channel_config.eq(&channel.channel_config))
+                        .returning(GeofenceSubscriberRecord::as_returning())
+                        .get_result(&mut conn)?;
+
+                subscribers.push(record.into());
+            }
 
-            Ok(record.into())
+            Ok(subscribers)
         })
         .await?
     }
 
-    /// Unsubscribe a user from a geofence
-    pub async fn remove_subscriber(&self, geofence_id: Uuid, user_id: Uuid) -> Result<bool> {
+    /// Unsubscribe a user from a geofence on a specific channel
+    pub async fn remove_subscriber(
+        &self,
+        geofence_id: Uuid,
+        user_id: Uuid,
+        channel: NotificationChannel,
+    ) -> Result<bool> {
         use geofence_subscribers::dsl;
 
         let pool = self.pool.clone();
@@ -665,7 +1125,8 @@ impl GeofenceRepository {
             let rows_affected = diesel::delete(
                 dsl::geofence_subscribers
                     .filter(dsl::geofence_id.eq(geofence_id))
-                    .filter(dsl::user_id.eq(user_id)),
+                    .filter(dsl::user_id.eq(user_id))
+                    .filter(dsl::channel.eq(channel)),
             )
             .execute(&mut conn)?;
 
@@ -674,7 +1135,7 @@ impl GeofenceRepository {
         .await?
     }
 
-    /// Get subscribers for a geofence
+    /// Get subscriber channels for a geofence
     pub async fn get_subscribers(&self, geofence_id: Uuid) -> Result<Vec<GeofenceSubscriber>> {
         use geofence_subscribers::dsl;
 
@@ -693,8 +1154,12 @@ impl GeofenceRepository {
         .await?
     }
 
-    /// Get subscribers who want email notifications
-    pub async fn get_subscribers_for_email(&self, geofence_id: Uuid) -> Result<Vec<Uuid>> {
+    /// Get users subscribed to a given channel for a geofence
+    pub async fn get_subscribers_for_channel(
+        &self,
+        geofence_id: Uuid,
+        channel: NotificationChannel,
+    ) -> Result<Vec<Uuid>> {
         use geofence_subscribers::dsl;
 
         let pool = self.pool.clone();
@@ -704,7 +1169,7 @@ impl GeofenceRepository {
 
             let user_ids: Vec<Uuid> = dsl::geofence_subscribers
                 .filter(dsl::geofence_id.eq(geofence_id))
-                .filter(dsl::send_email.eq(true))
+                .filter(dsl::channel.eq(channel))
                 .select(dsl::user_id)
                 .load(&mut conn)?;
 
@@ -713,6 +1178,12 @@ impl GeofenceRepository {
         .await?
     }
 
+    /// Get users subscribed to the email channel for a geofence
+    pub async fn get_subscribers_for_email(&self, geofence_id: Uuid) -> Result<Vec<Uuid>> {
+        self.get_subscribers_for_channel(geofence_id, NotificationChannel::Email)
+            .await
+    }
+
     // ==================== Exit Events ====================
 
     /// Create a geofence exit event
@@ -726,6 +1197,7 @@ impl GeofenceRepository {
         exit_latitude: f64,
         exit_longitude: f64,
         exit_altitude_msl_ft: Option<i32>,
+        exit_aircraft_category: Option<i16>,
         exit_layer: &GeofenceLayer,
     ) -> Result<GeofenceExitEvent> {
         use geofence_exit_events::dsl;
@@ -745,9 +1217,11 @@ impl GeofenceRepository {
                     dsl::exit_latitude.eq(exit_latitude),
                     dsl::exit_longitude.eq(exit_longitude),
                     dsl::exit_altitude_msl_ft.eq(exit_altitude_msl_ft),
+                    dsl::exit_aircraft_category.eq(exit_aircraft_category),
                     dsl::exit_layer_floor_ft.eq(exit_layer.floor_ft),
                     dsl::exit_layer_ceiling_ft.eq(exit_layer.ceiling_ft),
                     dsl::exit_layer_radius_nm.eq(exit_layer.radius_nm),
+                    dsl::delivery_counts.eq(serde_json::json!([])),
                 ))
                 .returning(GeofenceExitEventRecord::as_returning())
                 .get_result(&mut conn)?;
@@ -761,20 +1235,27 @@ impl GeofenceRepository {
                 exit_latitude: record.exit_latitude,
                 exit_longitude: record.exit_longitude,
                 exit_altitude_msl_ft: record.exit_altitude_msl_ft,
+                exit_aircraft_category: record.exit_aircraft_category,
                 exit_layer: GeofenceLayer {
                     floor_ft: record.exit_layer_floor_ft,
                     ceiling_ft: record.exit_layer_ceiling_ft,
                     radius_nm: record.exit_layer_radius_nm,
                 },
-                email_notifications_sent: record.email_notifications_sent,
+                delivery_counts: parse_delivery_counts(record.delivery_counts),
                 created_at: record.created_at,
             })
         })
         .await?
     }
 
-    /// Update the email count for an exit event
-    pub async fn update_exit_event_email_count(&self, id: Uuid, count: i32) -> Result<()> {
+    /// Set (replacing any prior value) how many deliveries have been
+    /// attempted on `channel` for an exit event.
+    pub async fn set_channel_delivery_count(
+        &self,
+        id: Uuid,
+        channel: NotificationChannel,
+        count: i32,
+    ) -> Result<()> {
         use geofence_exit_events::dsl;
 
         let pool = self.pool.clone();
@@ -782,11 +1263,24 @@ impl GeofenceRepository {
         tokio::task::spawn_blocking(move || -> Result<()> {
             let mut conn = pool.get()?;
 
-            diesel::update(dsl::geofence_exit_events.filter(dsl::id.eq(id)))
-                .set(dsl::email_notifications_sent.eq(count))
-                .execute(&mut conn)?;
+            conn.transaction::<_, anyhow::Error, _>(|conn| {
+                let current: JsonValue = dsl::geofence_exit_events
+                    .filter(dsl::id.eq(id))
+                    .select(dsl::delivery_counts)
+                    .get_result(conn)?;
 
-            Ok(())
+                let mut counts = parse_delivery_counts(current);
+                match counts.iter_mut().find(|c| c.channel == channel) {
+                    Some(entry) => entry.count = count,
+                    None => counts.push(ChannelDeliveryCount { channel, count }),
+                }
+
+                diesel::update(dsl::geofence_exit_events.filter(dsl::id.eq(id)))
+                    .set(dsl::delivery_counts.eq(serde_json::to_value(&counts)?))
+                    .execute(conn)?;
+
+                Ok(())
+            })
         })
         .await?
     }
@@ -828,12 +1322,13 @@ impl GeofenceRepository {
                     exit_latitude: r.exit_latitude,
                     exit_longitude: r.exit_longitude,
                     exit_altitude_msl_ft: r.exit_altitude_msl_ft,
+                    exit_aircraft_category: r.exit_aircraft_category,
                     exit_layer: GeofenceLayer {
                         floor_ft: r.exit_layer_floor_ft,
                         ceiling_ft: r.exit_layer_ceiling_ft,
                         radius_nm: r.exit_layer_radius_nm,
                     },
-                    email_notifications_sent: r.email_notifications_sent,
+                    delivery_counts: parse_delivery_counts(r.delivery_counts),
                     created_at: r.created_at,
                 })
                 .collect())
@@ -867,12 +1362,13 @@ impl GeofenceRepository {
                     exit_latitude: r.exit_latitude,
                     exit_longitude: r.exit_longitude,
                     exit_altitude_msl_ft: r.exit_altitude_msl_ft,
+                    exit_aircraft_category: r.exit_aircraft_category,
                     exit_layer: GeofenceLayer {
                         floor_ft: r.exit_layer_floor_ft,
                         ceiling_ft: r.exit_layer_ceiling_ft,
                         radius_nm: r.exit_layer_radius_nm,
                     },
-                    email_notifications_sent: r.email_notifications_sent,
+                    delivery_counts: parse_delivery_counts(r.delivery_counts),
                     created_at: r.created_at,
                 })
                 .collect())
@@ -909,12 +1405,113 @@ impl GeofenceRepository {
                     exit_latitude: r.exit_latitude,
                     exit_longitude: r.exit_longitude,
                     exit_altitude_msl_ft: r.exit_altitude_msl_ft,
+                    exit_aircraft_category: r.exit_aircraft_category,
                     exit_layer: GeofenceLayer {
                         floor_ft: r.exit_layer_floor_ft,
                         ceiling_ft: r.exit_layer_ceiling_ft,
                         radius_nm: r.exit_layer_radius_nm,
                     },
-                    email_notifications_sent: r.email_notifications_sent,
+                    delivery_counts: parse_delivery_counts(r.delivery_counts),
+                    created_at: r.created_at,
+                })
+                .collect())
+        })
+        .await?
+    }
+
+    // ==================== Entry Events ====================
+
+    /// Create a geofence entry event
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create_entry_event(
+        &self,
+        geofence_id: Uuid,
+        flight_id: Uuid,
+        aircraft_id: Uuid,
+        entry_time: DateTime<Utc>,
+        entry_latitude: f64,
+        entry_longitude: f64,
+        entry_altitude_msl_ft: Option<i32>,
+        entry_layer: &GeofenceLayer,
+    ) -> Result<GeofenceEntryEvent> {
+        use geofence_entry_events::dsl;
+
+        let pool = self.pool.clone();
+        let entry_layer = entry_layer.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<GeofenceEntryEvent> {
+            let mut conn = pool.get()?;
+
+            let record: GeofenceEntryEventRecord = diesel::insert_into(dsl::geofence_entry_events)
+                .values((
+                    dsl::geofence_id.eq(geofence_id),
+                    dsl::flight_id.eq(flight_id),
+                    dsl::aircraft_id.eq(aircraft_id),
+                    dsl::entry_time.eq(entry_time),
+                    dsl::entry_latitude.eq(entry_latitude),
+                    dsl::entry_longitude.eq(entry_longitude),
+                    dsl::entry_altitude_msl_ft.eq(entry_altitude_msl_ft),
+                    dsl::entry_layer_floor_ft.eq(entry_layer.floor_ft),
+                    dsl::entry_layer_ceiling_ft.eq(entry_layer.ceiling_ft),
+                    dsl::entry_layer_radius_nm.eq(entry_layer.radius_nm),
+                ))
+                .returning(GeofenceEntryEventRecord::as_returning())
+                .get_result(&mut conn)?;
+
+            Ok(GeofenceEntryEvent {
+                id: record.id,
+                geofence_id: record.geofence_id,
+                flight_id: record.flight_id,
+                aircraft_id: record.aircraft_id,
+                entry_time: record.entry_time,
+                entry_latitude: record.entry_latitude,
+                entry_longitude: record.entry_longitude,
+                entry_altitude_msl_ft: record.entry_altitude_msl_ft,
+                entry_layer: GeofenceLayer {
+                    floor_ft: record.entry_layer_floor_ft,
+                    ceiling_ft: record.entry_layer_ceiling_ft,
+                    radius_nm: record.entry_layer_radius_nm,
+                },
+                created_at: record.created_at,
+            })
+        })
+        .await?
+    }
+
+    /// Get entry events for a flight
+    pub async fn get_entry_events_for_flight(
+        &self,
+        flight_id: Uuid,
+    ) -> Result<Vec<GeofenceEntryEvent>> {
+        use geofence_entry_events::dsl;
+
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Vec<GeofenceEntryEvent>> {
+            let mut conn = pool.get()?;
+
+            let records: Vec<GeofenceEntryEventRecord> = dsl::geofence_entry_events
+                .filter(dsl::flight_id.eq(flight_id))
+                .order(dsl::entry_time.asc())
+                .select(GeofenceEntryEventRecord::as_select())
+                .load(&mut conn)?;
+
+            Ok(records
+                .into_iter()
+                .map(|r| GeofenceEntryEvent {
+                    id: r.id,
+                    geofence_id: r.geofence_id,
+                    flight_id: r.flight_id,
+                    aircraft_id: r.aircraft_id,
+                    entry_time: r.entry_time,
+                    entry_latitude: r.entry_latitude,
+                    entry_longitude: r.entry_longitude,
+                    entry_altitude_msl_ft: r.entry_altitude_msl_ft,
+                    entry_layer: GeofenceLayer {
+                        floor_ft: r.entry_layer_floor_ft,
+                        ceiling_ft: r.entry_layer_ceiling_ft,
+                        radius_nm: r.entry_layer_radius_nm,
+                    },
                     created_at: r.created_at,
                 })
                 .collect())
@@ -922,6 +1519,185 @@ impl GeofenceRepository {
         .await?
     }
 
+    /// Pair each entry for `flight_id` with the next exit recorded for the
+    /// same geofence, to compute how long the aircraft dwelt inside each
+    /// layer it entered. Entries and exits are correlated independently per
+    /// `geofence_id` (a flight can be tracked against several geofences at
+    /// once) and matched in chronological order: the Nth entry for a
+    /// geofence pairs with the Nth exit after it. An entry with no
+    /// corresponding exit yet gets a `None` `exit_time`/`dwell_seconds`.
+    pub async fn get_dwell_intervals_for_flight(
+        &self,
+        flight_id: Uuid,
+    ) -> Result<Vec<DwellInterval>> {
+        let entries = self.get_entry_events_for_flight(flight_id).await?;
+        let exits = self.get_exit_events_for_flight(flight_id).await?;
+
+        Ok(pair_entries_with_exits(entries, exits))
+    }
+
+    /// Query exit events within a time range with optional geofence/aircraft/
+    /// flight filters, for a breach-history dashboard. Returns a keyset-
+    /// paginated page of raw events (ordered by `(exit_time, id)`, so deep
+    /// history pages stay fast without `OFFSET`) plus a `COUNT(*)` series
+    /// bucketed by `filter.bucket` and `geofence_id` across the *whole*
+    /// filtered range, not just the current page.
+    pub async fn query_exit_events(&self, filter: ExitEventFilter) -> Result<ExitEventQueryResult> {
+        use diesel::sql_query;
+
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<ExitEventQueryResult> {
+            let mut conn = pool.get()?;
+
+            #[derive(QueryableByName, Debug)]
+            struct EventRow {
+                #[diesel(sql_type = sql_types::Uuid)]
+                id: Uuid,
+                #[diesel(sql_type = sql_types::Uuid)]
+                geofence_id: Uuid,
+                #[diesel(sql_type = sql_types::Uuid)]
+                flight_id: Uuid,
+                #[diesel(sql_type = sql_types::Uuid)]
+                aircraft_id: Uuid,
+                #[diesel(sql_type = sql_types::Timestamptz)]
+                exit_time: DateTime<Utc>,
+                #[diesel(sql_type = sql_types::Double)]
+                exit_latitude: f64,
+                #[diesel(sql_type = sql_types::Double)]
+                exit_longitude: f64,
+                #[diesel(sql_type = sql_types::Nullable<sql_types::Integer>)]
+                exit_altitude_msl_ft: Option<i32>,
+                #[diesel(sql_type = sql_types::Nullable<sql_types::SmallInt>)]
+                exit_aircraft_category: Option<i16>,
+                #[diesel(sql_type = sql_types::Integer)]
+                exit_layer_floor_ft: i32,
+                #[diesel(sql_type = sql_types::Integer)]
+                exit_layer_ceiling_ft: i32,
+                #[diesel(sql_type = sql_types::Double)]
+                exit_layer_radius_nm: f64,
+                #[diesel(sql_type = sql_types::Jsonb)]
+                delivery_counts: JsonValue,
+                #[diesel(sql_type = sql_types::Timestamptz)]
+                created_at: DateTime<Utc>,
+            }
+
+            // `page_size + 1` lets us tell whether another page follows
+            // without a separate COUNT query.
+            let rows: Vec<EventRow> = sql_query(
+                r#"
+                SELECT id, geofence_id, flight_id, aircraft_id, exit_time,
+                       exit_latitude, exit_longitude, exit_altitude_msl_ft,
+                       exit_aircraft_category,
+                       exit_layer_floor_ft, exit_layer_ceiling_ft, exit_layer_radius_nm,
+                       delivery_counts, created_at
+                FROM geofence_exit_events
+                WHERE exit_time BETWEEN $1 AND $2
+                  AND ($3::uuid IS NULL OR geofence_id = $3)
+                  AND ($4::uuid IS NULL OR aircraft_id = $4)
+                  AND ($5::uuid IS NULL OR flight_id = $5)
+                  AND ($6::timestamptz IS NULL OR (exit_time, id) > ($6, $7))
+                ORDER BY exit_time ASC, id ASC
+                LIMIT $8
+                "#,
+            )
+            .bind::<sql_types::Timestamptz, _>(filter.start_time)
+            .bind::<sql_types::Timestamptz, _>(filter.end_time)
+            .bind::<sql_types::Nullable<sql_types::Uuid>, _>(filter.geofence_id)
+            .bind::<sql_types::Nullable<sql_types::Uuid>, _>(filter.aircraft_id)
+            .bind::<sql_types::Nullable<sql_types::Uuid>, _>(filter.flight_id)
+            .bind::<sql_types::Nullable<sql_types::Timestamptz>, _>(filter.after.map(|c| c.exit_time))
+            .bind::<sql_types::Nullable<sql_types::Uuid>, _>(filter.after.map(|c| c.id))
+            .bind::<sql_types::BigInt, _>(filter.page_size + 1)
+            .load(&mut conn)?;
+
+            let has_more = rows.len() as i64 > filter.page_size;
+            let mut rows = rows;
+            if has_more {
+                rows.truncate(filter.page_size as usize);
+            }
+
+            let next_cursor = if has_more {
+                rows.last().map(|r| ExitEventCursor {
+                    exit_time: r.exit_time,
+                    id: r.id,
+                })
+            } else {
+                None
+            };
+
+            let events = rows
+                .into_iter()
+                .map(|r| GeofenceExitEvent {
+                    id: r.id,
+                    geofence_id: r.geofence_id,
+                    flight_id: r.flight_id,
+                    aircraft_id: r.aircraft_id,
+                    exit_time: r.exit_time,
+                    exit_latitude: r.exit_latitude,
+                    exit_longitude: r.exit_longitude,
+                    exit_altitude_msl_ft: r.exit_altitude_msl_ft,
+                    exit_aircraft_category: r.exit_aircraft_category,
+                    exit_layer: GeofenceLayer {
+                        floor_ft: r.exit_layer_floor_ft,
+                        ceiling_ft: r.exit_layer_ceiling_ft,
+                        radius_nm: r.exit_layer_radius_nm,
+                    },
+                    delivery_counts: parse_delivery_counts(r.delivery_counts),
+                    created_at: r.created_at,
+                })
+                .collect();
+
+            #[derive(QueryableByName, Debug)]
+            struct SeriesRow {
+                #[diesel(sql_type = sql_types::Timestamptz)]
+                bucket_start: DateTime<Utc>,
+                #[diesel(sql_type = sql_types::Uuid)]
+                geofence_id: Uuid,
+                #[diesel(sql_type = sql_types::BigInt)]
+                count: i64,
+            }
+
+            let series_rows: Vec<SeriesRow> = sql_query(
+                r#"
+                SELECT date_trunc($1, exit_time) AS bucket_start,
+                       geofence_id,
+                       COUNT(*) AS count
+                FROM geofence_exit_events
+                WHERE exit_time BETWEEN $2 AND $3
+                  AND ($4::uuid IS NULL OR geofence_id = $4)
+                  AND ($5::uuid IS NULL OR aircraft_id = $5)
+                  AND ($6::uuid IS NULL OR flight_id = $6)
+                GROUP BY bucket_start, geofence_id
+                ORDER BY bucket_start ASC, geofence_id ASC
+                "#,
+            )
+            .bind::<sql_types::Text, _>(filter.bucket.trunc_field())
+            .bind::<sql_types::Timestamptz, _>(filter.start_time)
+            .bind::<sql_types::Timestamptz, _>(filter.end_time)
+            .bind::<sql_types::Nullable<sql_types::Uuid>, _>(filter.geofence_id)
+            .bind::<sql_types::Nullable<sql_types::Uuid>, _>(filter.aircraft_id)
+            .bind::<sql_types::Nullable<sql_types::Uuid>, _>(filter.flight_id)
+            .load(&mut conn)?;
+
+            let series = series_rows
+                .into_iter()
+                .map(|r| ExitEventSeriesPoint {
+                    bucket_start: r.bucket_start,
+                    geofence_id: r.geofence_id,
+                    count: r.count,
+                })
+                .collect();
+
+            Ok(ExitEventQueryResult {
+                events,
+                series,
+                next_cursor,
+            })
+        })
+        .await?
+    }
+
     /// Check if a user owns a geofence
     pub async fn is_owner(&self, geofence_id: Uuid, user_id: Uuid) -> Result<bool> {
         use geofences::dsl;
@@ -964,4 +1740,393 @@ impl GeofenceRepository {
         })
         .await?
     }
+
+    /// Find geofences linked to `aircraft_id` that the aircraft is currently
+    /// *outside* of at `altitude_msl_ft`, pushing the containment math into
+    /// PostGIS instead of fetching every linked geofence and looping in Rust.
+    ///
+    /// For each linked geofence, `max_radius_meters` is used as a coarse
+    /// `ST_DWithin` pre-filter (backed by the spatial index on `center`)
+    /// before the per-layer check: the layers JSONB array is expanded with
+    /// `jsonb_array_elements`, filtered down to layers whose altitude band
+    /// contains `altitude_msl_ft`, and the innermost (smallest-radius) such
+    /// layer is checked with its own `ST_DWithin`. A geofence is returned,
+    /// along with the 0-based index of that innermost layer, when it has a
+    /// matching layer but the aircraft is not within that layer's radius.
+    /// Because of the pre-filter, only geofences whose `max_radius_meters`
+    /// circle still contains the position are considered: an aircraft beyond
+    /// every layer's radius is not returned.
+    pub async fn find_breaching_geofences(
+        &self,
+        aircraft_id: Uuid,
+        lat: f64,
+        lon: f64,
+        altitude_msl_ft: i32,
+    ) -> Result<Vec<(Geofence, usize)>> {
+        use diesel::sql_query;
+
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Vec<(Geofence, usize)>> {
+            let mut conn = pool.get()?;
+
+            #[derive(QueryableByName, Debug)]
+            struct Row {
+                #[diesel(sql_type = sql_types::Uuid)]
+                id: Uuid,
+                #[diesel(sql_type = sql_types::Text)]
+                name: String,
+                #[diesel(sql_type = sql_types::Nullable<sql_types::Text>)]
+                description: Option<String>,
+                #[diesel(sql_type = sql_types::Double)]
+                center_latitude: f64,
+                #[diesel(sql_type = sql_types::Double)]
+                center_longitude: f64,
+                #[diesel(sql_type = sql_types::Double)]
+                max_radius_meters: f64,
+                #[diesel(sql_type = sql_types::Jsonb)]
+                layers: JsonValue,
+                #[diesel(sql_type = sql_types::Nullable<sql_types::Integer>)]
+                max_altitude_msl_ft: Option<i32>,
+                #[diesel(sql_type = sql_types::Jsonb)]
+                ignored_categories: JsonValue,
+                #[diesel(sql_type = sql_types::Uuid)]
+                owner_user_id: Uuid,
+                #[diesel(sql_type = sql_types::Nullable<sql_types::Uuid>)]
+                club_id: Option<Uuid>,
+                #[diesel(sql_type = sql_types::Timestamptz)]
+                created_at: DateTime<Utc>,
+                #[diesel(sql_type = sql_types::Timestamptz)]
+                updated_at: DateTime<Utc>,
+                #[diesel(sql_type = sql_types::BigInt)]
+                layer_idx: i64,
+            }
+
+            let rows: Vec<Row> = sql_query(
+                r#"
+                SELECT g.id, g.name, g.description,
+                       ST_Y(g.center) AS center_latitude,
+                       ST_X(g.center) AS center_longitude,
+                       g.max_radius_meters, g.layers,
+                       g.max_altitude_msl_ft, g.ignored_categories,
+                       g.owner_user_id, g.club_id,
+                       g.created_at, g.updated_at,
+                       layer.layer_idx
+                FROM geofences g
+                INNER JOIN aircraft_geofences ag ON ag.geofence_id = g.id
+                INNER JOIN LATERAL (
+                    SELECT elem.ord - 1 AS layer_idx,
+                           (elem.value->>'radiusNm')::double precision AS radius_nm
+                    FROM jsonb_array_elements(g.layers) WITH ORDINALITY AS elem(value, ord)
+                    WHERE $4 BETWEEN (elem.value->>'floorFt')::int AND (elem.value->>'ceilingFt')::int
+                    ORDER BY (elem.value->>'radiusNm')::double precision ASC
+                    LIMIT 1
+                ) layer ON true
+                WHERE ag.aircraft_id = $1
+                  AND g.deleted_at IS NULL
+                  AND ST_DWithin(
+                      g.center::geography,
+                      ST_SetSRID(ST_MakePoint($2, $3), 4326)::geography,
+                      g.max_radius_meters
+                  )
+                  AND NOT ST_DWithin(
+                      g.center::geography,
+                      ST_SetSRID(ST_MakePoint($2, $3), 4326)::geography,
+                      layer.radius_nm * 1852
+                  )
+                "#,
+            )
+            .bind::<sql_types::Uuid, _>(aircraft_id)
+            .bind::<sql_types::Double, _>(lon)
+            .bind::<sql_types::Double, _>(lat)
+            .bind::<sql_types::Integer, _>(altitude_msl_ft)
+            .load(&mut conn)?;
+
+            rows.into_iter()
+                .map(|r| {
+                    let layers: Vec<GeofenceLayer> = serde_json::from_value(r.layers)?;
+                    let ignored_categories: Vec<i16> =
+                        serde_json::from_value(r.ignored_categories)?;
+                    Ok((
+                        Geofence {
+                            id: r.id,
+                            name: r.name,
+                            description: r.description,
+                            center_latitude: r.center_latitude,
+                            center_longitude: r.center_longitude,
+                            max_radius_meters: r.max_radius_meters,
+                            layers,
+                            max_altitude_msl_ft: r.max_altitude_msl_ft,
+                            ignored_categories,
+                            owner_user_id: r.owner_user_id,
+                            club_id: r.club_id,
+                            created_at: r.created_at,
+                            updated_at: r.updated_at,
+                        },
+                        r.layer_idx as usize,
+                    ))
+                })
+                .collect()
+        })
+        .await?
+    }
+
+    // ==================== Notification Job Queue ====================
+    //
+    // A durable, at-least-once delivery pipeline for exit-event notifications.
+    // Workers call `claim_next_job` in a loop (`FOR UPDATE SKIP LOCKED` lets
+    // many workers drain the queue concurrently without double-delivery),
+    // periodically call `heartbeat` while working a job, and finish with
+    // `complete_job` or `fail_job`. A separate sweep calls `reap_stale_jobs`
+    // to recover jobs left `running` by a worker that crashed mid-send.
+
+    /// Enqueue a notification job for a subscriber of an exit event on a
+    /// specific channel. `job` carries whatever payload that channel needs
+    /// to deliver - e.g. for `Webhook`, the destination URL and pre-rendered
+    /// body built from the exit event.
+    pub async fn enqueue_notification_job(
+        &self,
+        exit_event_id: Uuid,
+        subscriber_user_id: Uuid,
+        channel: NotificationChannel,
+        job: JsonValue,
+    ) -> Result<GeofenceNotificationJob> {
+        use geofence_notification_jobs::dsl;
+
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<GeofenceNotificationJob> {
+            let mut conn = pool.get()?;
+
+            let record: GeofenceNotificationJobRecord =
+                diesel::insert_into(dsl::geofence_notification_jobs)
+                    .values((
+                        dsl::exit_event_id.eq(exit_event_id),
+                        dsl::subscriber_user_id.eq(subscriber_user_id),
+                        dsl::channel.eq(channel),
+                        dsl::job.eq(job),
+                        dsl::status.eq(JobStatus::New),
+                    ))
+                    .returning(GeofenceNotificationJobRecord::as_returning())
+                    .get_result(&mut conn)?;
+
+            Ok(record.into())
+        })
+        .await?
+    }
+
+    /// Atomically claim the oldest claimable job (status `new` and not
+    /// backed off past `heartbeat`), marking it `running` with a fresh
+    /// heartbeat. Returns `None` if there is nothing to claim.
+    pub async fn claim_next_job(&self) -> Result<Option<GeofenceNotificationJob>> {
+        use diesel::sql_query;
+
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Option<GeofenceNotificationJob>> {
+            let mut conn = pool.get()?;
+
+            #[derive(QueryableByName, Debug)]
+            struct Row {
+                #[diesel(sql_type = sql_types::Uuid)]
+                id: Uuid,
+                #[diesel(sql_type = sql_types::Uuid)]
+                exit_event_id: Uuid,
+                #[diesel(sql_type = sql_types::Uuid)]
+                subscriber_user_id: Uuid,
+                #[diesel(sql_type = crate::schema::sql_types::NotificationChannel)]
+                channel: NotificationChannel,
+                #[diesel(sql_type = sql_types::Jsonb)]
+                job: JsonValue,
+                #[diesel(sql_type = crate::schema::sql_types::JobStatus)]
+                status: JobStatus,
+                #[diesel(sql_type = sql_types::Integer)]
+                attempts: i32,
+                #[diesel(sql_type = sql_types::Nullable<sql_types::Timestamptz>)]
+                heartbeat: Option<DateTime<Utc>>,
+                #[diesel(sql_type = sql_types::Timestamptz)]
+                created_at: DateTime<Utc>,
+            }
+
+            let row: Option<Row> = sql_query(
+                r#"
+                UPDATE geofence_notification_jobs
+                SET status = 'running', heartbeat = now()
+                WHERE id = (
+                    SELECT id FROM geofence_notification_jobs
+                    WHERE status = 'new' AND (heartbeat IS NULL OR heartbeat <= now())
+                    ORDER BY created_at
+                    LIMIT 1
+                    FOR UPDATE SKIP LOCKED
+                )
+                RETURNING id, exit_event_id, subscriber_user_id, channel, job, status, attempts, heartbeat, created_at
+                "#,
+            )
+            .get_result(&mut conn)
+            .optional()?;
+
+            Ok(row.map(|r| GeofenceNotificationJob {
+                id: r.id,
+                exit_event_id: r.exit_event_id,
+                subscriber_user_id: r.subscriber_user_id,
+                channel: r.channel,
+                job: r.job,
+                status: r.status,
+                attempts: r.attempts,
+                heartbeat: r.heartbeat,
+                created_at: r.created_at,
+            }))
+        })
+        .await?
+    }
+
+    /// Record that a worker is still actively processing a `running` job.
+    pub async fn heartbeat(&self, id: Uuid) -> Result<()> {
+        use geofence_notification_jobs::dsl;
+
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<()> {
+            let mut conn = pool.get()?;
+
+            diesel::update(dsl::geofence_notification_jobs.filter(dsl::id.eq(id)))
+                .set(dsl::heartbeat.eq(Utc::now()))
+                .execute(&mut conn)?;
+
+            Ok(())
+        })
+        .await?
+    }
+
+    /// Reset jobs stuck `running` with a heartbeat older than `threshold` ago
+    /// back to `new`, so another worker can pick them up. This recovers jobs
+    /// orphaned by a crashed worker.
+    pub async fn reap_stale_jobs(&self, threshold: chrono::Duration) -> Result<usize> {
+        use geofence_notification_jobs::dsl;
+
+        let pool = self.pool.clone();
+        let cutoff = Utc::now() - threshold;
+
+        tokio::task::spawn_blocking(move || -> Result<usize> {
+            let mut conn = pool.get()?;
+
+            let rows_affected = diesel::update(
+                dsl::geofence_notification_jobs
+                    .filter(dsl::status.eq(JobStatus::Running))
+                    .filter(dsl::heartbeat.lt(cutoff)),
+            )
+            .set((dsl::status.eq(JobStatus::New), dsl::heartbeat.eq(None::<DateTime<Utc>>)))
+            .execute(&mut conn)?;
+
+            Ok(rows_affected)
+        })
+        .await?
+    }
+
+    /// Mark a job permanently done and remove it from the queue.
+    pub async fn complete_job(&self, id: Uuid) -> Result<()> {
+        use geofence_notification_jobs::dsl;
+
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<()> {
+            let mut conn = pool.get()?;
+
+            diesel::delete(dsl::geofence_notification_jobs.filter(dsl::id.eq(id)))
+                .execute(&mut conn)?;
+
+            Ok(())
+        })
+        .await?
+    }
+
+    /// Record a failed attempt. If `attempts` is still under `max_attempts`,
+    /// the job goes back to `new` with an exponential backoff (capped at 1
+    /// hour) before it can be claimed again; otherwise it is dropped from
+    /// the queue as permanently failed.
+    pub async fn fail_job(&self, id: Uuid, max_attempts: i32) -> Result<()> {
+        use geofence_notification_jobs::dsl;
+
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<()> {
+            let mut conn = pool.get()?;
+
+            let attempts: i32 = diesel::update(dsl::geofence_notification_jobs.filter(dsl::id.eq(id)))
+                .set(dsl::attempts.eq(dsl::attempts + 1))
+                .returning(dsl::attempts)
+                .get_result(&mut conn)?;
+
+            if attempts >= max_attempts {
+                diesel::delete(dsl::geofence_notification_jobs.filter(dsl::id.eq(id)))
+                    .execute(&mut conn)?;
+                return Ok(());
+            }
+
+            let backoff_secs = (30_i64 * 2_i64.pow(attempts.max(0) as u32)).min(3600);
+            let next_attempt = Utc::now() + chrono::Duration::seconds(backoff_secs);
+
+            diesel::update(dsl::geofence_notification_jobs.filter(dsl::id.eq(id)))
+                .set((dsl::status.eq(JobStatus::New), dsl::heartbeat.eq(Some(next_attempt))))
+                .execute(&mut conn)?;
+
+            Ok(())
+        })
+        .await?
+    }
+}
+
+/// Deserialize a `delivery_counts` Jsonb column, treating anything
+/// unparseable (including a legacy/empty value) as "no deliveries recorded
+/// yet" rather than failing the whole row.
+fn parse_delivery_counts(value: JsonValue) -> Vec<ChannelDeliveryCount> {
+    serde_json::from_value(value).unwrap_or_default()
+}
+
+/// Pair `entries` and `exits` (both already sorted ascending by time) into
+/// dwell intervals, matched independently per `geofence_id`. For each
+/// geofence, the Nth entry pairs with the Nth exit that occurs after it;
+/// an entry with no following exit yields a `None` `exit_time`/
+/// `dwell_seconds` (still inside, or the exit was never recorded).
+fn pair_entries_with_exits(
+    entries: Vec<GeofenceEntryEvent>,
+    exits: Vec<GeofenceExitEvent>,
+) -> Vec<DwellInterval> {
+    use std::collections::HashMap;
+
+    let mut exits_by_geofence: HashMap<Uuid, std::collections::VecDeque<DateTime<Utc>>> =
+        HashMap::new();
+    for exit in exits {
+        exits_by_geofence
+            .entry(exit.geofence_id)
+            .or_default()
+            .push_back(exit.exit_time);
+    }
+
+    entries
+        .into_iter()
+        .map(|entry| {
+            let exit_time = exits_by_geofence
+                .get_mut(&entry.geofence_id)
+                .and_then(|queue| {
+                    while let Some(front) = queue.front() {
+                        if *front >= entry.entry_time {
+                            break;
+                        }
+                        queue.pop_front();
+                    }
+                    queue.pop_front()
+                });
+
+            let dwell_seconds =
+                exit_time.map(|exit_time| (exit_time - entry.entry_time).num_seconds());
+
+            DwellInterval {
+                geofence_id: entry.geofence_id,
+                entry_time: entry.entry_time,
+                exit_time,
+                dwell_seconds,
+            }
+        })
+        .collect()
 }
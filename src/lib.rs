@@ -22,8 +22,10 @@ pub mod analytics_repo;
 pub mod aprs_client;
 pub mod aprs_filters;
 pub mod aprs_nats_publisher;
+pub mod archive_digest;
 pub mod archive_email_reporter;
 pub mod archive_service;
+pub mod archive_telemetry;
 pub mod auth;
 pub mod beast;
 pub mod beast_consumer_task;
@@ -33,9 +35,13 @@ pub mod clubs_repo;
 pub mod coverage;
 pub mod coverage_cache;
 pub mod coverage_repo;
+pub mod coverage_stream;
+pub mod dkim;
 pub mod elevation;
 pub mod email;
+pub mod email_templates;
 pub mod email_reporter;
+pub mod email_spool;
 pub mod faa;
 pub mod fetch_receivers;
 pub mod fix_processor;
@@ -69,6 +75,8 @@ pub mod runways_repo;
 pub mod schema;
 pub mod server_messages;
 pub mod server_messages_repo;
+pub mod telemetry;
+pub mod terrain;
 pub mod user_fixes;
 pub mod user_fixes_repo;
 pub mod users;
@@ -13,9 +13,9 @@ mod commands;
 mod migration_email_reporter;
 
 use commands::{
-    handle_archive, handle_dump_unified_ddb, handle_ingest_adsb, handle_ingest_ogn,
-    handle_load_data, handle_pull_airspaces, handle_pull_data, handle_resurrect, handle_run,
-    handle_seed_test_data, handle_sitemap_generation,
+    handle_archive, handle_archive_digest, handle_dump_unified_ddb, handle_ingest_adsb,
+    handle_ingest_ogn, handle_load_data, handle_pull_airspaces, handle_pull_data,
+    handle_resurrect, handle_run, handle_seed_test_data, handle_sitemap_generation,
 };
 use migration_email_reporter::{
     MigrationEmailConfig, MigrationReport, send_migration_email_report,
@@ -264,6 +264,21 @@ enum Commands {
         #[arg(long)]
         archive_path: String,
     },
+    /// Send an aggregate digest email summarizing archive runs over a trailing window
+    ///
+    /// Reads the JSON exports written by `archive` when `ARCHIVE_EXPORT_METRICS=true` and
+    /// builds per-table trend lines, cumulative bytes, and anomaly flags across the window.
+    /// Only actually sends once `ARCHIVE_DIGEST_CADENCE_DAYS` (default 7) have passed since
+    /// the last digest, so this is safe to invoke on the same cadence as `archive` itself.
+    ArchiveDigest {
+        /// Directory where archive report JSON exports are stored
+        #[arg(long)]
+        archive_path: String,
+
+        /// Number of trailing days of archive runs to include in the digest
+        #[arg(long, default_value_t = 30)]
+        window_days: i64,
+    },
     /// Verify runtime initialization (Sentry, tracing, tokio-console)
     ///
     /// Tests that the runtime can initialize without panicking. Used for CI/CD
@@ -319,6 +334,45 @@ enum Commands {
         #[arg(long, default_value = "3,4,5,6,7,8", value_delimiter = ',')]
         resolutions: Vec<i16>,
     },
+    /// Rebuild weekly/monthly coverage rollup tables from daily coverage
+    ///
+    /// Aggregates `receiver_coverage_h3` rows into `receiver_coverage_h3_weekly`
+    /// and `receiver_coverage_h3_monthly`, summing fix counts, taking MIN/MAX of
+    /// altitude bounds, and recomputing the fix-count-weighted average altitude
+    /// across each bin. `get_coverage_in_bbox`/`get_coverage_geojson` read from
+    /// these automatically for wide date ranges.
+    ///
+    /// If start/end dates are omitted, defaults to the last 90 days ending
+    /// yesterday. Safe to re-run for an overlapping range.
+    ///
+    /// Example: soar aggregate-coverage-rollups --start-date 2025-01-01 --end-date 2025-12-31
+    AggregateCoverageRollups {
+        /// Start date to roll up from (YYYY-MM-DD). If omitted, defaults to 90 days before end date.
+        #[arg(long)]
+        start_date: Option<chrono::NaiveDate>,
+
+        /// End date to roll up to (YYYY-MM-DD). If omitted, defaults to yesterday.
+        #[arg(long)]
+        end_date: Option<chrono::NaiveDate>,
+    },
+    /// Backfill ground elevation and land-cover terrain class onto coverage hexes
+    ///
+    /// Tags `receiver_coverage_h3` rows that haven't been enriched yet with ground
+    /// elevation (from the existing elevation service) and, if a land-cover URL is
+    /// given, a coarse terrain class (water/flat/hilly/mountainous/urban). Lets
+    /// `get_coverage_in_bbox` filter on terrain class and terrain clearance so
+    /// clients can distinguish true radio coverage gaps from terrain shadowing.
+    /// Safe to re-run: already-enriched hexes are skipped, and the reference data
+    /// is only re-downloaded once a newer file is available.
+    ///
+    /// Example: soar enrich-coverage-terrain --landcover-url https://example.com/landcover.csv
+    EnrichCoverageTerrain {
+        /// URL of a `h3_index,terrain_class` CSV land-cover reference data set
+        /// (see `soar::terrain::ReferenceDataset`). If omitted, only ground
+        /// elevation is backfilled.
+        #[arg(long)]
+        landcover_url: Option<String>,
+    },
     /// Dump unified FlarmNet device database to JSONL file
     ///
     /// Downloads the unified FlarmNet database from <https://turbo87.github.io/united-flarmnet/united.fln>
@@ -990,6 +1044,13 @@ async fn main() -> Result<()> {
             // DumpUnifiedDdb only downloads and exports data, doesn't need database
             return handle_dump_unified_ddb(output.clone(), source.clone()).await;
         }
+        Commands::ArchiveDigest {
+            archive_path,
+            window_days,
+        } => {
+            // ArchiveDigest only reads JSON exports from disk and sends email, doesn't need database
+            return handle_archive_digest(archive_path.clone(), *window_days).await;
+        }
         _ => {
             // All other commands need database access
         }
@@ -1063,11 +1124,14 @@ async fn main() -> Result<()> {
         Commands::Migrate {} => "soar-migrate",
         Commands::SeedTestData {} => "soar-seed-test-data",
         Commands::AggregateCoverage { .. } => "soar-aggregate-coverage",
+        Commands::AggregateCoverageRollups { .. } => "soar-aggregate-coverage-rollups",
+        Commands::EnrichCoverageTerrain { .. } => "soar-enrich-coverage-terrain",
         // These should not reach here due to early returns
         Commands::IngestOgn { .. } => unreachable!(),
         Commands::IngestAdsb { .. } => unreachable!(),
         Commands::VerifyRuntime { .. } => unreachable!(),
         Commands::DumpUnifiedDdb { .. } => unreachable!(),
+        Commands::ArchiveDigest { .. } => unreachable!(),
     };
 
     // For Migrate command, handle errors specially to send notifications
@@ -1240,6 +1304,10 @@ async fn main() -> Result<()> {
             // This should never be reached due to early return above
             unreachable!("VerifyRuntime should be handled before database setup")
         }
+        Commands::ArchiveDigest { .. } => {
+            // This should never be reached due to early return above
+            unreachable!("ArchiveDigest should be handled before database setup")
+        }
         Commands::Migrate {} => {
             // Migrations are already run by setup_diesel_database()
             // Send email notification and Sentry event
@@ -1314,6 +1382,13 @@ async fn main() -> Result<()> {
             commands::aggregate_coverage(diesel_pool, start_date, end_date, resolutions.clone())
                 .await
         }
+        Commands::AggregateCoverageRollups {
+            start_date,
+            end_date,
+        } => commands::aggregate_coverage_rollups(diesel_pool, start_date, end_date).await,
+        Commands::EnrichCoverageTerrain { landcover_url } => {
+            commands::enrich_coverage_terrain(diesel_pool, landcover_url).await
+        }
         Commands::SeedTestData {} => handle_seed_test_data(&diesel_pool).await,
         Commands::DumpUnifiedDdb { .. } => {
             // This should never be reached due to early return above
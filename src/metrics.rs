@@ -16,6 +16,12 @@ pub struct AprsIngestHealth {
     pub aprs_connected: bool,
     pub jetstream_connected: bool,
     pub last_message_time: Option<Instant>,
+    /// Number of consecutive NATS connect/ingestion failures in the current backoff run.
+    pub nats_consecutive_failures: u32,
+    /// Backoff delay (milliseconds) applied before the next NATS reconnect attempt.
+    pub nats_backoff_ms: u64,
+    /// Set once `nats_consecutive_failures` crosses the circuit-breaker threshold.
+    pub nats_circuit_open: bool,
 }
 
 static APRS_HEALTH: OnceLock<Arc<RwLock<AprsIngestHealth>>> = OnceLock::new();
@@ -242,6 +248,37 @@ pub async fn analytics_metrics_task(pool: crate::web::PgPool) {
     }
 }
 
+/// Background task to report connection pool utilization
+/// Updates gauges for total/idle/in-use connections every 15 seconds
+pub async fn pool_metrics_task(pool: crate::web::PgPool) {
+    loop {
+        let state = pool.state();
+        let in_use = state.connections.saturating_sub(state.idle_connections);
+
+        metrics::gauge!("db_pool.connections").set(state.connections as f64);
+        metrics::gauge!("db_pool.idle_connections").set(state.idle_connections as f64);
+        metrics::gauge!("db_pool.in_use_connections").set(in_use as f64);
+
+        tokio::time::sleep(Duration::from_secs(15)).await;
+    }
+}
+
+/// Initialize coverage pipeline metrics to zero/default values
+/// This ensures metrics always appear in Prometheus queries even if no events have occurred
+pub fn initialize_coverage_metrics() {
+    metrics::counter!("coverage.repo.upsert_records_total").absolute(0);
+    metrics::counter!("coverage.repo.upsert_chunks_total").absolute(0);
+    metrics::histogram!("coverage.repo.upsert_batch_ms").record(0.0);
+
+    metrics::histogram!("coverage.repo.bbox_query_ms").record(0.0);
+    metrics::counter!("coverage.repo.hexes_returned_total").increment(0);
+    metrics::counter!("coverage.repo.results_capped_total").increment(0);
+
+    metrics::gauge!("db_pool.connections").set(0.0);
+    metrics::gauge!("db_pool.idle_connections").set(0.0);
+    metrics::gauge!("db_pool.in_use_connections").set(0.0);
+}
+
 /// Initialize APRS ingest metrics to zero/default values
 /// This ensures metrics always appear in Prometheus queries even if no events have occurred
 pub fn initialize_aprs_ingest_metrics() {
@@ -282,6 +319,11 @@ pub fn initialize_aprs_ingest_metrics() {
     metrics::counter!("aprs.shutdown.queue_depth_at_shutdown").absolute(0);
     metrics::counter!("aprs.shutdown.messages_flushed").absolute(0);
     metrics::histogram!("aprs.shutdown.flush_duration_seconds").record(0.0);
+
+    // NATS connection backoff/circuit-breaker metrics
+    metrics::gauge!("aprs.nats.reconnect.consecutive_failures").set(0.0);
+    metrics::gauge!("aprs.nats.reconnect.backoff_ms").set(0.0);
+    metrics::gauge!("aprs.nats.reconnect.circuit_open").set(0.0);
 }
 
 /// Initialize Beast ingest metrics to zero/default values
@@ -146,6 +146,25 @@ pub enum AdsbEmitterCategory {
     C5, // Line obstacle
 }
 
+impl AdsbEmitterCategory {
+    /// The numeric subtype code for Category A (fixed-wing aircraft), as per
+    /// DO-260B - e.g. `2` for Small, `5` for Heavy. This is what consumers
+    /// mean when they refer to "emitter category 2-5"; categories B/C have
+    /// no numeric analogue and return `None`.
+    pub fn numeric_code(&self) -> Option<i16> {
+        match self {
+            AdsbEmitterCategory::A1 => Some(1),
+            AdsbEmitterCategory::A2 => Some(2),
+            AdsbEmitterCategory::A3 => Some(3),
+            AdsbEmitterCategory::A4 => Some(4),
+            AdsbEmitterCategory::A5 => Some(5),
+            AdsbEmitterCategory::A6 => Some(6),
+            AdsbEmitterCategory::A7 => Some(7),
+            _ => None,
+        }
+    }
+}
+
 impl fmt::Display for AdsbEmitterCategory {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let s = match self {
@@ -57,6 +57,10 @@ pub mod sql_types {
     #[diesel(postgres_type(name = "geometry"))]
     pub struct Geometry;
 
+    #[derive(diesel::query_builder::QueryId, Clone, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "job_status"))]
+    pub struct JobStatus;
+
     #[derive(diesel::query_builder::QueryId, Clone, diesel::sql_types::SqlType)]
     #[diesel(postgres_type(name = "light_sport_type"))]
     pub struct LightSportType;
@@ -65,6 +69,10 @@ pub mod sql_types {
     #[diesel(postgres_type(name = "message_source"))]
     pub struct MessageSource;
 
+    #[derive(diesel::query_builder::QueryId, Clone, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "notification_channel"))]
+    pub struct NotificationChannel;
+
     #[derive(diesel::query_builder::QueryId, Clone, diesel::sql_types::SqlType)]
     #[diesel(postgres_type(name = "point", schema = "pg_catalog"))]
     pub struct Point;
@@ -876,6 +884,43 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::JobStatus;
+    use super::sql_types::NotificationChannel;
+
+    geofence_notification_jobs (id) {
+        id -> Uuid,
+        exit_event_id -> Uuid,
+        subscriber_user_id -> Uuid,
+        channel -> NotificationChannel,
+        job -> Jsonb,
+        status -> JobStatus,
+        attempts -> Int4,
+        heartbeat -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+
+    geofence_entry_events (id) {
+        id -> Uuid,
+        geofence_id -> Uuid,
+        flight_id -> Uuid,
+        aircraft_id -> Uuid,
+        entry_time -> Timestamptz,
+        entry_latitude -> Float8,
+        entry_longitude -> Float8,
+        entry_altitude_msl_ft -> Nullable<Int4>,
+        entry_layer_floor_ft -> Int4,
+        entry_layer_ceiling_ft -> Int4,
+        entry_layer_radius_nm -> Float8,
+        created_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     use diesel::sql_types::*;
     use super::sql_types::Point;
@@ -1368,6 +1413,8 @@ diesel::allow_tables_to_appear_in_same_query!(
     flight_duration_buckets,
     flight_pilots,
     flights,
+    geofence_entry_events,
+    geofence_notification_jobs,
     locations,
     raw_messages,
     raw_messages_default,
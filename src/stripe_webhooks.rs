@@ -15,6 +15,16 @@ pub struct StripeWebhookEventModel {
     pub processing_error: Option<String>,
     pub payload: serde_json::Value,
     pub created_at: DateTime<Utc>,
+    /// Number of processing attempts that have failed so far.
+    pub retry_count: i32,
+    /// Earliest time a failed event may be retried; `NULL` until the first
+    /// scheduled retry. It is not cleared when the event is later processed
+    /// or dead-lettered; `processed` and `dead_lettered` keep it out of retry
+    /// polling instead.
+    pub next_retry_at: Option<DateTime<Utc>>,
+    /// Set once `retry_count` exceeds the configured max attempts - the
+    /// event is permanently abandoned and no longer polled for retry.
+    pub dead_lettered: bool,
 }
 
 /// Insert model for new webhook events
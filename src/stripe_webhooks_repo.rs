@@ -1,9 +1,14 @@
 use anyhow::Result;
+use chrono::Utc;
 use diesel::prelude::*;
+use rand::Rng;
 
 use crate::stripe_webhooks::{NewStripeWebhookEvent, StripeWebhookEventModel};
 use crate::web::PgPool;
 
+/// Default ceiling on retry attempts before an event is dead-lettered.
+pub const DEFAULT_MAX_RETRY_ATTEMPTS: i32 = 5;
+
 #[derive(Clone)]
 pub struct StripeWebhookEventsRepository {
     pool: PgPool,
@@ -84,21 +89,109 @@ impl StripeWebhookEventsRepository {
         Ok(())
     }
 
-    /// Mark an event as failed with an error message
-    pub async fn mark_failed(&self, stripe_event_id: &str, error: &str) -> Result<()> {
+    /// Record a processing failure and either schedule a retry with
+    /// exponential backoff + jitter, or dead-letter the event once
+    /// `max_attempts` has been exceeded.
+    ///
+    /// `delay = min(base_delay * 2^retry_count, max_delay)`, then multiplied
+    /// by a uniform random factor in `[0.5, 1.0]` so a batch of events that
+    /// fail together don't all retry in lockstep.
+    pub async fn mark_for_retry(
+        &self,
+        stripe_event_id: &str,
+        error: &str,
+        base_delay: chrono::Duration,
+        max_delay: chrono::Duration,
+        max_attempts: i32,
+    ) -> Result<()> {
         use crate::schema::stripe_webhook_events;
 
         let pool = self.pool.clone();
         let stripe_event_id = stripe_event_id.to_string();
         let error = error.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let mut conn = pool.get()?;
+
+            let retry_count: i32 = diesel::update(stripe_webhook_events::table)
+                .filter(stripe_webhook_events::stripe_event_id.eq(&stripe_event_id))
+                .set((
+                    stripe_webhook_events::processing_error.eq(Some(&error)),
+                    stripe_webhook_events::retry_count.eq(stripe_webhook_events::retry_count + 1),
+                ))
+                .returning(stripe_webhook_events::retry_count)
+                .get_result(&mut conn)?;
+
+            if retry_count > max_attempts {
+                diesel::update(stripe_webhook_events::table)
+                    .filter(stripe_webhook_events::stripe_event_id.eq(&stripe_event_id))
+                    .set(stripe_webhook_events::dead_lettered.eq(true))
+                    .execute(&mut conn)?;
+
+                return Ok::<(), anyhow::Error>(());
+            }
+
+            let backoff = (base_delay * 2i32.pow(retry_count.max(0) as u32)).min(max_delay);
+            let jitter: f64 = rand::rng().random_range(0.5..=1.0);
+            let jittered =
+                chrono::Duration::milliseconds((backoff.num_milliseconds() as f64 * jitter) as i64);
+            let next_retry_at = Utc::now() + jittered;
+
+            diesel::update(stripe_webhook_events::table)
+                .filter(stripe_webhook_events::stripe_event_id.eq(&stripe_event_id))
+                .set(stripe_webhook_events::next_retry_at.eq(Some(next_retry_at)))
+                .execute(&mut conn)?;
+
+            Ok::<(), anyhow::Error>(())
+        })
+        .await??;
+
+        Ok(())
+    }
+
+    /// Atomically claim up to `limit` events due for retry (`processed =
+    /// false AND dead_lettered = false AND next_retry_at <= now()`), oldest
+    /// first, for a worker to re-invoke the handler on.
+    pub async fn claim_due_retries(&self, limit: i64) -> Result<Vec<StripeWebhookEventModel>> {
+        use crate::schema::stripe_webhook_events::dsl;
+
+        let pool = self.pool.clone();
+
+        let result = tokio::task::spawn_blocking(move || {
+            let mut conn = pool.get()?;
+
+            let events: Vec<StripeWebhookEventModel> = dsl::stripe_webhook_events
+                .filter(dsl::processed.eq(false))
+                .filter(dsl::dead_lettered.eq(false))
+                .filter(dsl::next_retry_at.le(Utc::now()))
+                .order(dsl::next_retry_at.asc())
+                .limit(limit)
+                .select(StripeWebhookEventModel::as_select())
+                .load(&mut conn)?;
+
+            Ok::<Vec<StripeWebhookEventModel>, anyhow::Error>(events)
+        })
+        .await??;
+
+        Ok(result)
+    }
+
+    /// Permanently abandon an event: no further retries will be attempted.
+    pub async fn dead_letter(&self, stripe_event_id: &str, error: &str) -> Result<()> {
+        use crate::schema::stripe_webhook_events;
+
+        let pool = self.pool.clone();
+        let stripe_event_id = stripe_event_id.to_string();
+        let error = error.to_string();
+
         tokio::task::spawn_blocking(move || {
             let mut conn = pool.get()?;
 
             diesel::update(stripe_webhook_events::table)
                 .filter(stripe_webhook_events::stripe_event_id.eq(&stripe_event_id))
                 .set((
-                    stripe_webhook_events::processed.eq(true),
                     stripe_webhook_events::processing_error.eq(Some(&error)),
+                    stripe_webhook_events::dead_lettered.eq(true),
                 ))
                 .execute(&mut conn)?;
 
@@ -0,0 +1,291 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel::sql_types;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+use crate::web::PgPool;
+
+/// Coarse land-cover classification for a coverage hex, derived from the
+/// downloaded land-cover reference data set. Lets operators tell a true
+/// radio coverage gap from one explained by terrain shadowing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TerrainClass {
+    Water,
+    Flat,
+    Hilly,
+    Mountainous,
+    Urban,
+}
+
+impl TerrainClass {
+    /// Decode the `terrain_class` column (see the `add_coverage_terrain_data`
+    /// migration). Returns `None` for `NULL` or an unrecognized code rather
+    /// than erroring, since this is a best-effort enrichment field.
+    pub fn from_i16(value: i16) -> Option<Self> {
+        match value {
+            0 => Some(Self::Water),
+            1 => Some(Self::Flat),
+            2 => Some(Self::Hilly),
+            3 => Some(Self::Mountainous),
+            4 => Some(Self::Urban),
+            _ => None,
+        }
+    }
+
+    pub fn as_i16(self) -> i16 {
+        match self {
+            Self::Water => 0,
+            Self::Flat => 1,
+            Self::Hilly => 2,
+            Self::Mountainous => 3,
+            Self::Urban => 4,
+        }
+    }
+}
+
+/// A downloadable land-cover/terrain reference data set, identified by a
+/// stable `name` (the manifest primary key) and the URL it's currently
+/// fetched from.
+///
+/// The expected file format is a CSV of `h3_index,terrain_class` rows (H3
+/// index as a signed i64, `terrain_class` as the codes in
+/// [`TerrainClass::from_i16`]) at a fixed, coarse H3 resolution — producing
+/// this from a raw land-cover raster is an offline preprocessing step (e.g.
+/// with `h3-py`), not something this loader does itself.
+#[derive(Debug, Clone)]
+pub struct ReferenceDataset {
+    pub name: &'static str,
+    pub url: String,
+}
+
+/// In-memory H3-keyed lookup for the most recently loaded land-cover
+/// reference data set. Cheap to clone (an `Arc` around the backing map);
+/// `terrain_refresh_task` swaps the whole map in on each successful
+/// refresh, and `CoverageRepository::enrich_terrain` reads it while tagging
+/// coverage hexes.
+#[derive(Clone, Default)]
+pub struct TerrainLookup {
+    by_h3_index: Arc<RwLock<HashMap<i64, TerrainClass>>>,
+}
+
+impl TerrainLookup {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub async fn get(&self, h3_index: i64) -> Option<TerrainClass> {
+        self.by_h3_index.read().await.get(&h3_index).copied()
+    }
+
+    /// Replace the whole lookup table with a freshly loaded data set.
+    async fn replace(&self, data: HashMap<i64, TerrainClass>) {
+        *self.by_h3_index.write().await = data;
+    }
+
+    pub async fn len(&self) -> usize {
+        self.by_h3_index.read().await.len()
+    }
+}
+
+/// Queryable row for the `terrain_reference_datasets` manifest table. Raw
+/// SQL rather than the diesel query builder, matching `coverage_repo.rs` —
+/// this table isn't in `schema.rs` either.
+#[derive(QueryableByName, Debug)]
+struct ManifestRow {
+    #[diesel(sql_type = sql_types::Timestamptz)]
+    last_loaded_at: DateTime<Utc>,
+}
+
+/// Tracks which reference data sets have been loaded and when, so
+/// `terrain_refresh_task` only re-downloads a URL once a newer file is
+/// available and a restart doesn't re-ingest an already-loaded one.
+#[derive(Clone)]
+pub struct TerrainManifestRepository {
+    pool: PgPool,
+}
+
+impl TerrainManifestRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// `last_loaded_at` for a data set, or `None` if it has never been
+    /// loaded.
+    pub async fn last_loaded_at(&self, name: &'static str) -> Result<Option<DateTime<Utc>>> {
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let mut conn = pool.get()?;
+
+            let rows: Vec<ManifestRow> = diesel::sql_query(
+                "SELECT last_loaded_at FROM terrain_reference_datasets WHERE name = $1",
+            )
+            .bind::<sql_types::Text, _>(name)
+            .load(&mut conn)?;
+
+            Ok(rows.into_iter().next().map(|r| r.last_loaded_at))
+        })
+        .await?
+    }
+
+    /// Record that `name` was successfully (re)loaded from `url` at `at`.
+    pub async fn mark_loaded(
+        &self,
+        name: &'static str,
+        url: String,
+        at: DateTime<Utc>,
+    ) -> Result<()> {
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let mut conn = pool.get()?;
+
+            diesel::sql_query(
+                r#"
+                INSERT INTO terrain_reference_datasets (name, url, last_loaded_at)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (name) DO UPDATE SET
+                    url = EXCLUDED.url,
+                    last_loaded_at = EXCLUDED.last_loaded_at
+                "#,
+            )
+            .bind::<sql_types::Text, _>(name)
+            .bind::<sql_types::Text, _>(url)
+            .bind::<sql_types::Timestamptz, _>(at)
+            .execute(&mut conn)
+            .context("Failed to record terrain reference data set as loaded")?;
+
+            Ok(())
+        })
+        .await?
+    }
+}
+
+/// Parse the `h3_index,terrain_class` CSV body of a reference data set (see
+/// [`ReferenceDataset`]). Malformed lines are skipped with a warning rather
+/// than failing the whole load, since one bad row shouldn't sideline an
+/// otherwise-usable refresh.
+fn parse_reference_csv(body: &str) -> HashMap<i64, TerrainClass> {
+    let mut data = HashMap::new();
+
+    for line in body.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let Some((h3_str, class_str)) = line.split_once(',') else {
+            warn!("Skipping malformed terrain reference row: {line}");
+            continue;
+        };
+
+        let (Ok(h3_index), Ok(class_code)) = (
+            h3_str.trim().parse::<i64>(),
+            class_str.trim().parse::<i16>(),
+        ) else {
+            warn!("Skipping malformed terrain reference row: {line}");
+            continue;
+        };
+
+        match TerrainClass::from_i16(class_code) {
+            Some(class) => {
+                data.insert(h3_index, class);
+            }
+            None => warn!("Skipping terrain reference row with unknown class {class_code}"),
+        }
+    }
+
+    data
+}
+
+/// Download `dataset` and load it into `lookup` if the manifest doesn't
+/// already have an entry at least as new as the remote `Last-Modified`
+/// header. Idempotent: re-running against an unchanged remote file is a
+/// cheap no-op after the `HEAD` request.
+pub async fn refresh_reference_data(
+    manifest: &TerrainManifestRepository,
+    lookup: &TerrainLookup,
+    dataset: &ReferenceDataset,
+) -> Result<()> {
+    let head = reqwest::Client::new()
+        .head(&dataset.url)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .with_context(|| format!("HEAD {}", dataset.url))?;
+
+    let last_modified = head
+        .headers()
+        .get(reqwest::header::LAST_MODIFIED)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| DateTime::parse_from_rfc2822(v).ok())
+        .map(|dt| dt.with_timezone(&Utc));
+
+    if let (Some(last_modified), Some(last_loaded_at)) =
+        (last_modified, manifest.last_loaded_at(dataset.name).await?)
+        && last_modified <= last_loaded_at
+    {
+        info!(
+            "Terrain reference data set '{}' is up to date (last modified {})",
+            dataset.name, last_modified
+        );
+        return Ok(());
+    }
+
+    info!(
+        "Downloading terrain reference data set '{}' from {}",
+        dataset.name, dataset.url
+    );
+
+    let body = reqwest::get(&dataset.url)
+        .await
+        .and_then(|r| r.error_for_status())
+        .with_context(|| format!("GET {}", dataset.url))?
+        .text()
+        .await
+        .with_context(|| format!("read body {}", dataset.url))?;
+
+    let data = parse_reference_csv(&body);
+    let loaded_count = data.len();
+    lookup.replace(data).await;
+
+    manifest
+        .mark_loaded(dataset.name, dataset.url.clone(), Utc::now())
+        .await?;
+
+    info!(
+        "Loaded terrain reference data set '{}': {} H3 cells",
+        dataset.name, loaded_count
+    );
+
+    Ok(())
+}
+
+/// Background task that periodically refreshes every configured reference
+/// data set. A failed refresh is logged and retried on the next tick rather
+/// than aborting the task, matching the resilience of the repo's other
+/// long-running background loops (e.g. `crate::metrics::analytics_metrics_task`).
+pub async fn terrain_refresh_task(
+    manifest: TerrainManifestRepository,
+    lookup: TerrainLookup,
+    datasets: Vec<ReferenceDataset>,
+    interval: std::time::Duration,
+) {
+    loop {
+        for dataset in &datasets {
+            if let Err(e) = refresh_reference_data(&manifest, &lookup, dataset).await {
+                warn!(
+                    "Failed to refresh terrain reference data set '{}': {:#}",
+                    dataset.name, e
+                );
+            }
+        }
+
+        tokio::time::sleep(interval).await;
+    }
+}
@@ -26,6 +26,7 @@ use tower_http::cors::CorsLayer;
 use tracing::{error, info, warn};
 
 use crate::actions;
+use crate::coverage_stream::CoverageStreamService;
 use crate::live_fixes::LiveFixService;
 
 // Embed web assets into the binary
@@ -68,6 +69,11 @@ pub type PgPool = Pool<ConnectionManager<PgConnection>>;
 pub struct AppState {
     pub pool: PgPool,                             // Diesel pool for all operations
     pub live_fix_service: Option<LiveFixService>, // Live fix service for WebSocket subscriptions
+    pub coverage_stream: CoverageStreamService,   // Live coverage updates for SSE subscriptions
+    // Shared across requests (rather than constructed per-request) so its
+    // geofence_cache is the one actually kept coherent by the cache
+    // invalidation listener started in `start_web_server`.
+    pub geofence_repo: crate::geofence_repo::GeofenceRepository,
 }
 
 async fn handle_static_file(uri: Uri, request: Request<Body>) -> Response {
@@ -566,12 +572,61 @@ pub async fn start_web_server(interface: String, port: u16, pool: PgPool) -> Res
     // Initialize airspace metrics
     crate::metrics::initialize_airspace_metrics();
 
+    // Initialize coverage pipeline metrics
+    crate::metrics::initialize_coverage_metrics();
+
     // Start process metrics background task
     tokio::spawn(crate::metrics::process_metrics_task());
 
     // Start analytics metrics background task
     tokio::spawn(crate::metrics::analytics_metrics_task(pool.clone()));
 
+    // Start connection pool utilization background task
+    tokio::spawn(crate::metrics::pool_metrics_task(pool.clone()));
+
+    // Start terrain reference-data refresh and coverage enrichment background
+    // tasks if a land-cover data set is configured (see crate::terrain and
+    // CoverageRepository::enrich_terrain). Ground elevation still comes from
+    // the existing ElevationService either way, so enrichment only needs
+    // ELEVATION_DATA_PATH (already required for AGL processing) to be useful.
+    match crate::elevation::ElevationDB::new() {
+        Ok(elevation) => {
+            let terrain_lookup = crate::terrain::TerrainLookup::new();
+
+            if let Ok(landcover_url) = std::env::var("TERRAIN_LANDCOVER_URL") {
+                info!("TERRAIN_LANDCOVER_URL found, starting terrain reference data refresh task");
+                let manifest = crate::terrain::TerrainManifestRepository::new(pool.clone());
+                let datasets = vec![crate::terrain::ReferenceDataset {
+                    name: "landcover",
+                    url: landcover_url,
+                }];
+                let refresh_interval = std::env::var("TERRAIN_REFRESH_INTERVAL_SECS")
+                    .ok()
+                    .and_then(|s| s.parse().ok())
+                    .map(std::time::Duration::from_secs)
+                    .unwrap_or(std::time::Duration::from_secs(86400));
+
+                tokio::spawn(crate::terrain::terrain_refresh_task(
+                    manifest,
+                    terrain_lookup.clone(),
+                    datasets,
+                    refresh_interval,
+                ));
+            } else {
+                warn!(
+                    "TERRAIN_LANDCOVER_URL not configured, coverage hexes will only be tagged with ground elevation"
+                );
+            }
+
+            tokio::spawn(crate::coverage_repo::coverage_terrain_enrichment_task(
+                pool.clone(),
+                elevation,
+                terrain_lookup,
+            ));
+        }
+        Err(e) => warn!("Failed to initialize ElevationDB, skipping terrain enrichment: {e}"),
+    }
+
     info!("Starting web server on {}:{}", interface, port);
 
     // Initialize live fix service if NATS_URL is configured
@@ -595,11 +650,51 @@ pub async fn start_web_server(interface: String, port: u16, pool: PgPool) -> Res
         }
     };
 
+    // Start listening for live coverage updates (see coverage_stream) so the
+    // SSE endpoint has somewhere to pull from as soon as the server is up
+    let coverage_stream = CoverageStreamService::new();
+    coverage_stream.spawn_listener();
+
+    // Build one long-lived GeofenceRepository and start its cache
+    // invalidation listener, so the `geofence_changed` NOTIFY channel (see
+    // notify_geofence_changed in geofence_repo.rs) actually keeps this
+    // cache coherent instead of every caller getting its own never-invalidated
+    // cache.
+    let geofence_repo = crate::geofence_repo::GeofenceRepository::new(pool.clone());
+    geofence_repo.spawn_cache_invalidation_listener();
+
     let app_state = AppState {
         pool,
         live_fix_service,
+        coverage_stream,
+        geofence_repo,
     };
 
+    // Start the Stripe webhook retry poller if Stripe is configured, so
+    // events queued by `mark_for_retry` actually get drained (see
+    // crate::actions::stripe_connect::retry_next_stripe_webhook).
+    match crate::stripe_client::StripeConfig::from_env() {
+        Ok(stripe_config) => {
+            tokio::spawn(crate::actions::stripe_connect::stripe_webhook_retry_task(
+                app_state.clone(),
+                stripe_config,
+            ));
+        }
+        Err(e) => warn!("Stripe not configured, skipping webhook retry poller: {e}"),
+    }
+
+    // Start the geofence notification job poller, so webhook jobs queued by
+    // `process_geofence_exits` actually get delivered; queued SMS/push jobs
+    // are completed without a delivery attempt (see
+    // crate::flight_tracker::geofence_alerts::process_next_notification_job).
+    tokio::spawn(
+        crate::flight_tracker::geofence_alerts::geofence_notification_job_poller(
+            app_state.geofence_repo.clone(),
+            reqwest::Client::new(),
+            5,
+        ),
+    );
+
     // Create CORS layer that allows all origins and methods
     let cors_layer = CorsLayer::permissive();
 
@@ -618,6 +713,11 @@ pub async fn start_web_server(interface: String, port: u16, pool: PgPool) -> Res
         .route("/clubs/{id}/flights", get(actions::get_club_flights))
         .route("/fixes", get(actions::search_fixes))
         .route("/fixes/live", get(actions::fixes_live_websocket))
+        .route("/coverage/stream", get(actions::get_coverage_stream))
+        .route(
+            "/coverage/tiles/{z}/{x}/{y}",
+            get(actions::get_coverage_tile),
+        )
         .route("/flights", get(actions::search_flights))
         .route("/flights/{id}", get(actions::get_flight_by_id))
         .route("/flights/{id}/device", get(actions::get_flight_device))
@@ -628,6 +728,50 @@ pub async fn start_web_server(interface: String, port: u16, pool: PgPool) -> Res
             get(actions::get_flight_spline_path),
         )
         .route("/flights/{id}/nearby", get(actions::get_nearby_flights))
+        // Geofence routes
+        .route(
+            "/geofences",
+            get(actions::list_geofences).post(actions::create_geofence),
+        )
+        .route(
+            "/geofences/{id}",
+            get(actions::get_geofence)
+                .put(actions::update_geofence)
+                .delete(actions::delete_geofence),
+        )
+        .route(
+            "/geofences/{id}/aircraft",
+            get(actions::get_geofence_aircraft).post(actions::add_geofence_aircraft),
+        )
+        .route(
+            "/geofences/{geofence_id}/aircraft/{aircraft_id}",
+            delete(actions::remove_geofence_aircraft),
+        )
+        .route(
+            "/geofences/{id}/subscribers",
+            get(actions::get_geofence_subscribers).post(actions::subscribe_to_geofence),
+        )
+        .route(
+            "/geofences/{geofence_id}/subscribers/{user_id}",
+            delete(actions::unsubscribe_from_geofence),
+        )
+        .route("/geofences/{id}/events", get(actions::get_geofence_events))
+        .route(
+            "/geofences/exit-events/query",
+            post(actions::query_exit_events),
+        )
+        .route(
+            "/flights/{id}/geofence-events",
+            get(actions::get_flight_geofence_events),
+        )
+        .route(
+            "/flights/{id}/geofence-entries",
+            get(actions::get_flight_geofence_entries),
+        )
+        .route(
+            "/flights/{id}/geofence-dwell",
+            get(actions::get_flight_geofence_dwell),
+        )
         // Pilot routes
         .route("/pilots", post(actions::create_pilot))
         .route("/pilots/{id}", get(actions::get_pilot_by_id))